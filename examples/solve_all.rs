@@ -0,0 +1,73 @@
+//! Plays every word of the default dictionary as the hidden answer and
+//! reports how many guesses `auto_solve` needed.
+//!
+//! ```text
+//! cargo run --release --example solve_all -- [frequency|entropy|minimax|coverage]
+//! ```
+//!
+//! The strategies other than `frequency` score every candidate against every
+//! other, so expect them to take a while.
+
+use std::collections::BTreeMap;
+use std::process;
+
+use wordl::{Strategy, Wordl};
+
+fn parse_strategy(name: &str) -> Option<Strategy> {
+    match name {
+        "frequency" => Some(Strategy::Frequency),
+        "entropy" => Some(Strategy::Entropy),
+        "minimax" => Some(Strategy::Minimax),
+        "coverage" => Some(Strategy::LetterCoverage),
+        _ => None,
+    }
+}
+
+fn main() {
+    let name = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "frequency".to_string());
+    let strategy = parse_strategy(&name).unwrap_or_else(|| {
+        eprintln!("unknown strategy {:?}", name);
+        eprintln!("usage: solve_all [frequency|entropy|minimax|coverage]");
+        process::exit(2);
+    });
+
+    let mut w = Wordl::with_default_dictionary();
+    w.set_strategy(strategy);
+    let answers = w.candidates();
+
+    // guess count -> number of answers solved in that many guesses
+    let mut histogram: BTreeMap<usize, usize> = BTreeMap::new();
+    let mut failed = 0;
+    for answer in &answers {
+        w.reset();
+        let guesses = w.auto_solve(answer);
+        if guesses.last() == Some(answer) {
+            *histogram.entry(guesses.len()).or_insert(0) += 1;
+        } else {
+            failed += 1;
+        }
+    }
+
+    let solved: usize = histogram.values().sum();
+    let total: usize = histogram.iter().map(|(n, count)| n * count).sum();
+    let within_six: usize = histogram.range(..=6).map(|(_, count)| count).sum();
+    println!("strategy: {}", name);
+    println!("answers:  {}", answers.len());
+    for (n, count) in &histogram {
+        println!("{:>3} guesses: {}", n, count);
+    }
+    if failed > 0 {
+        println!("unsolved: {}", failed);
+    }
+    println!("mean:     {:.3}", total as f64 / solved.max(1) as f64);
+    println!(
+        "max:      {}",
+        histogram.keys().next_back().copied().unwrap_or(0)
+    );
+    println!(
+        "within 6: {:.1}%",
+        100.0 * within_six as f64 / answers.len().max(1) as f64
+    );
+}
@@ -0,0 +1,179 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::OnceLock;
+
+use crate::Wordl;
+
+/// The best opening guess for a dictionary and the best follow-up to each
+/// feedback it can get, see `Wordl::opening_book`. A book is only good for
+/// the word lists it was computed from, which `dictionary_hash` identifies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpeningBook {
+    dictionary_hash: u64,
+    first: String,
+    // feedback to the first guess, e.g. "MCMMH" -> the second guess
+    responses: BTreeMap<String, String>,
+}
+
+impl OpeningBook {
+    /// The `Wordl::dictionary_hash` of the solver the book was computed for.
+    pub fn dictionary_hash(&self) -> u64 {
+        self.dictionary_hash
+    }
+
+    pub fn first(&self) -> &str {
+        &self.first
+    }
+
+    /// The second guess after `feedback`, in `M`/`C`/`H` form, to the first.
+    pub fn response(&self, feedback: &str) -> Option<&str> {
+        self.responses.get(feedback).map(String::as_str)
+    }
+}
+
+impl<const N: usize> Wordl<N> {
+    /// Identifies the answers and allowed words, so that an `OpeningBook`
+    /// saved for one word list is never used with another. Stable across
+    /// runs and platforms.
+    pub fn dictionary_hash(&self) -> u64 {
+        // 64-bit FNV-1a, with a separator after each word and between the lists
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut feed = |bytes: &[u8]| {
+            for b in bytes {
+                hash ^= u64::from(*b);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        };
+        for word in &self.dictionary {
+            feed(word.as_bytes());
+            feed(b"\n");
+        }
+        feed(b"\0");
+        for word in &self.allowed {
+            feed(word.as_bytes());
+            feed(b"\n");
+        }
+        hash
+    }
+
+    /// The opening book for the current words: `best_first_guess` and, for
+    /// every feedback it can get, the allowed word with the most expected
+    /// information against the answers left. Computed once, as expensive as
+    /// `suggest_any_by_entropy` over every answer, and cached until words are
+    /// added.
+    pub fn opening_book(&self) -> &OpeningBook {
+        self.book.get_or_init(|| {
+            let first = self.best_first_guess();
+            let mut groups: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+            for answer in &self.dictionary {
+                let feedback = Self::evaluate(answer, &first).feedback();
+                groups.entry(feedback).or_default().insert(answer.clone());
+            }
+            let responses = groups
+                .into_iter()
+                .map(|(feedback, answers)| {
+                    let best = Self::rank_by_entropy_over(
+                        &answers,
+                        &answers,
+                        &[None; N],
+                        &self.allowed,
+                        1,
+                    )
+                    .pop()
+                    .unwrap_or_default();
+                    (feedback, best)
+                })
+                .collect();
+            OpeningBook {
+                dictionary_hash: self.dictionary_hash(),
+                first,
+                responses,
+            }
+        })
+    }
+
+    /// Uses a book saved from an earlier run, returning false and leaving
+    /// things unchanged if it was computed for different words.
+    pub fn set_opening_book(&mut self, book: OpeningBook) -> bool {
+        if book.dictionary_hash != self.dictionary_hash() {
+            return false;
+        }
+        self.first_guess = OnceLock::from(book.first.clone());
+        self.book = OnceLock::from(book);
+        true
+    }
+
+    /// The guess to play from the opening book: its first move before any
+    /// guesses, its response after the first move. Anywhere else, or in hard
+    /// mode where the book's responses might not be allowed, this is the top
+    /// `suggest_any_by_entropy`.
+    pub fn opening_move(&self) -> String {
+        if !self.hard_mode {
+            match self.guesses.as_slice() {
+                [] => return self.best_first_guess(),
+                [first] if first.letters() == self.best_first_guess() => {
+                    let book = self.opening_book();
+                    if let Some(response) = book.response(&first.feedback()) {
+                        return response.to_string();
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.suggest_any_by_entropy(1).pop().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Wordl;
+
+    fn small() -> Wordl {
+        let mut w = Wordl::new();
+        for word in [
+            "cants", "pants", "rants", "wants", "bowel", "dowel", "towel", "vowel", "slump",
+        ] {
+            w.insert(word.to_string());
+        }
+        for word in ["caper", "bated", "bract"] {
+            w.insert_allowed(word.to_string());
+        }
+        w
+    }
+
+    #[test]
+    fn book_moves_match_fresh_computation() {
+        let mut w = small();
+        let book = w.opening_book().clone();
+        // a second solver with nothing cached picks the first move afresh
+        let fresh = small().suggest_any_by_entropy(1).pop().unwrap();
+        assert_eq!(book.first(), fresh);
+        assert_eq!(book.first(), "bract");
+        assert_eq!(w.opening_move(), book.first());
+
+        for answer in ["pants", "vowel", "slump"] {
+            let mut game = w.clone();
+            game.guess(Wordl::evaluate(answer, book.first()));
+            let fresh = game.suggest_any_by_entropy(1).pop().unwrap();
+            assert_eq!(game.opening_move(), fresh, "{}", answer);
+        }
+
+        // out of the book the move is computed as usual
+        w.guess(Wordl::evaluate("pants", "slump"));
+        assert_eq!(w.opening_move(), w.suggest_any_by_entropy(1)[0]);
+    }
+
+    #[test]
+    fn books_only_load_for_their_own_words() {
+        let w = small();
+        let book = w.opening_book().clone();
+        let mut same = small();
+        assert!(same.set_opening_book(book.clone()));
+        assert_eq!(same.opening_book(), &book);
+
+        let mut other = small();
+        other.insert("mould".to_string());
+        assert_ne!(other.dictionary_hash(), w.dictionary_hash());
+        assert!(!other.set_opening_book(book));
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{Strategy, Wordl, WordlError};
+
+/// Configures a `Wordl` beyond what `Wordl::default()` offers. The word
+/// length is the `N` parameter of the solver being built.
+///
+/// ```
+/// use wordl::{Strategy, Wordl};
+///
+/// let w: Wordl = Wordl::builder()
+///     .answers(["slump", "souls"])
+///     .allowed(["crane"])
+///     .scorer(Strategy::Entropy)
+///     .hard_mode(true)
+///     .build()
+///     .unwrap();
+/// assert_eq!(w.remaining_count(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WordlBuilder<const N: usize = 5> {
+    answers: Vec<String>,
+    allowed: Vec<String>,
+    hard_mode: bool,
+    strategy: Strategy,
+    alphabet: Option<Vec<char>>,
+}
+
+impl<const N: usize> Default for WordlBuilder<N> {
+    fn default() -> Self {
+        WordlBuilder {
+            answers: Vec::default(),
+            allowed: Vec::default(),
+            hard_mode: false,
+            strategy: Strategy::default(),
+            alphabet: None,
+        }
+    }
+}
+
+impl<const N: usize> WordlBuilder<N> {
+    /// Adds possible answers, which are also allowed guesses.
+    pub fn answers<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.answers.extend(words.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds words that may be guessed but are never the answer.
+    pub fn allowed<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed.extend(words.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn hard_mode(mut self, hard_mode: bool) -> Self {
+        self.hard_mode = hard_mode;
+        self
+    }
+
+    /// Chooses how `suggest` ranks candidates.
+    pub fn scorer(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Restricts words to the given letters, see `Wordl::set_alphabet`.
+    pub fn alphabet<I: IntoIterator<Item = char>>(mut self, alphabet: I) -> Self {
+        self.alphabet = Some(alphabet.into_iter().collect());
+        self
+    }
+
+    /// Builds the solver. Unlike `Wordl::insert`, a word that isn't `N`
+    /// letters long is an error rather than skipped, and at least one answer
+    /// is required.
+    pub fn build(self) -> Result<Wordl<N>, WordlError> {
+        if let Some(word) = self
+            .answers
+            .iter()
+            .chain(&self.allowed)
+            .find(|word| word.trim().chars().count() != N)
+        {
+            return Err(WordlError::BadWordLength {
+                word: word.clone(),
+                expected: N,
+            });
+        }
+        let mut w = Wordl::from_lists(self.answers, self.allowed);
+        if let Some(alphabet) = self.alphabet {
+            w.set_alphabet(alphabet);
+        }
+        if w.remaining_count() == 0 {
+            return Err(WordlError::EmptyDictionary);
+        }
+        w.set_hard_mode(self.hard_mode);
+        w.set_strategy(self.strategy);
+        Ok(w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Strategy, Wordl, WordlError};
+
+    #[test]
+    fn builds_a_configured_solver() {
+        let w = Wordl::<6>::builder()
+            .answers(["planet", "plants"])
+            .allowed(["mirror"])
+            .hard_mode(true)
+            .scorer(Strategy::Minimax)
+            .build()
+            .unwrap();
+        assert!(w.hard_mode());
+        assert_eq!(w.strategy(), Strategy::Minimax);
+        assert_eq!(w.candidates(), vec!["planet", "plants"]);
+        assert_eq!(w.suggest_any(3).len(), 3);
+    }
+
+    #[test]
+    fn rejects_words_of_the_wrong_length() {
+        let result = Wordl::<5>::builder().answers(["slump", "planet"]).build();
+        assert!(matches!(
+            result,
+            Err(WordlError::BadWordLength { expected: 5, .. })
+        ));
+        let result = Wordl::<5>::builder().build();
+        assert!(matches!(result, Err(WordlError::EmptyDictionary)));
+    }
+}
@@ -0,0 +1,4055 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::RandomState;
+use std::collections::{btree_set, BTreeMap, BTreeSet, BinaryHeap};
+use std::fmt::{self, Debug, Display};
+use std::fs::File;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+mod book;
+pub use book::OpeningBook;
+
+mod builder;
+pub use builder::WordlBuilder;
+
+mod multi;
+pub use multi::MultiWordl;
+
+mod patterns;
+
+mod report;
+pub use report::DifficultyReport;
+
+mod scorer;
+use scorer::ProbeFrequency;
+pub use scorer::Scorer;
+
+#[cfg(feature = "tui")]
+pub mod tui;
+
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "wasm")]
+pub use wasm::WasmWordl;
+
+/// Feedback for a single letter of a guess.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Letter {
+    Hit(char),
+    Miss(char),
+    Contains(char),
+    /// A letter whose feedback isn't known yet, e.g. in a partly copied row.
+    /// It doesn't constrain the answer at all.
+    Unknown(char),
+}
+
+impl Letter {
+    /// Pairs a guessed letter with its feedback character, as `Word::parse`
+    /// reads them: `M`, `C`, `H` or `?`, or the matching square.
+    ///
+    /// ```
+    /// use wordl::Letter;
+    ///
+    /// assert_eq!(Letter::from_pair('H', 's'), Ok(Letter::Hit('s')));
+    /// assert!(Letter::from_pair('X', 's').is_err());
+    /// ```
+    pub fn from_pair(feedback: char, letter: char) -> Result<Letter, ParseError> {
+        match feedback {
+            'M' | '⬛' | '⬜' => Ok(Letter::Miss(letter)),
+            'C' | '🟨' => Ok(Letter::Contains(letter)),
+            'H' | '🟩' => Ok(Letter::Hit(letter)),
+            '?' | '❔' => Ok(Letter::Unknown(letter)),
+            _ => Err(ParseError::InvalidFeedback(feedback)),
+        }
+    }
+}
+
+// Hits are uppercase, Contains lowercase, Misses an underscore and Unknowns
+// a question mark
+impl Display for Letter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Letter::Hit(c) => c.to_uppercase().try_for_each(|c| write!(f, "{}", c)),
+            Letter::Contains(c) => write!(f, "{}", c),
+            Letter::Miss(_) => write!(f, "_"),
+            Letter::Unknown(_) => write!(f, "?"),
+        }
+    }
+}
+
+/// A guessed word, one `Letter` of feedback per position. The word length
+/// `N` defaults to the classic five letters.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Word<const N: usize = 5>(pub [Letter; N]);
+
+// serde can't derive for arrays of a generic length, so a word is represented
+// as a sequence of its letters
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for Word<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for Word<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let letters = Vec::<Letter>::deserialize(deserializer)?;
+        let len = letters.len();
+        letters
+            .try_into()
+            .map(Word)
+            .map_err(|_| serde::de::Error::invalid_length(len, &format!("{} letters", N).as_str()))
+    }
+}
+
+impl<const N: usize> From<[Letter; N]> for Word<N> {
+    fn from(letters: [Letter; N]) -> Self {
+        Word(letters)
+    }
+}
+
+impl<const N: usize> Word<N> {
+    // the feedback as an `M`/`C`/`H` string, the inverse of `parse`
+    fn feedback(&self) -> String {
+        self.0
+            .iter()
+            .map(|l| match l {
+                Letter::Miss(_) => 'M',
+                Letter::Contains(_) => 'C',
+                Letter::Hit(_) => 'H',
+                Letter::Unknown(_) => '?',
+            })
+            .collect()
+    }
+
+    // the guessed letters, lowercase
+    fn letters(&self) -> String {
+        self.0
+            .iter()
+            .map(|l| match l {
+                Letter::Miss(c) | Letter::Contains(c) | Letter::Hit(c) | Letter::Unknown(c) => *c,
+            })
+            .collect()
+    }
+
+    /// Builds a `Word` from a guess and its feedback, where each feedback
+    /// character is one of `M` (miss), `C` (contains), `H` (hit) or `?`
+    /// (unknown), or the matching square pasted from a result grid (see
+    /// `from_emoji_row`). Both are trimmed and the guess is lowercased.
+    ///
+    /// ```
+    /// use wordl::{Letter, Word};
+    ///
+    /// let word: Word = Word::parse("souls", "HMCCC").unwrap();
+    /// assert!(matches!(word.0[0], Letter::Hit('s')));
+    /// ```
+    pub fn parse(guess: &str, feedback: &str) -> Result<Word<N>, ParseError> {
+        let guess: Vec<char> = guess.trim().to_lowercase().chars().collect();
+        let feedback: Vec<char> = feedback
+            .trim()
+            .chars()
+            // some platforms follow the squares with an emoji presentation selector
+            .filter(|c| *c != '\u{fe0f}')
+            .collect();
+        if guess.len() != N || feedback.len() != N {
+            return Err(ParseError::WrongLength);
+        }
+        let letters = guess
+            .into_iter()
+            .zip(feedback)
+            .map(|(c, f)| Letter::from_pair(f, c))
+            .collect::<Result<Vec<_>, _>>()?;
+        letters
+            .try_into()
+            .map(Word)
+            .map_err(|_| ParseError::WrongLength)
+    }
+
+    /// The feedback as a row of a result grid, the inverse of
+    /// `from_emoji_row`.
+    pub fn emoji_row(&self) -> String {
+        self.0
+            .iter()
+            .map(|l| match l {
+                Letter::Miss(_) => '⬛',
+                Letter::Contains(_) => '🟨',
+                Letter::Hit(_) => '🟩',
+                Letter::Unknown(_) => '❔',
+            })
+            .collect()
+    }
+
+    /// Builds a `Word` from a guess and a row of a shared result grid: 🟩 is
+    /// a hit, 🟨 contains, ⬛ or ⬜ a miss and ❔ unknown.
+    ///
+    /// ```
+    /// use wordl::Word;
+    ///
+    /// let word: Word = Word::from_emoji_row("souls", "🟩⬛🟨🟨⬜").unwrap();
+    /// assert_eq!(word, Word::parse("souls", "HMCCM").unwrap());
+    /// ```
+    pub fn from_emoji_row(guess: &str, row: &str) -> Result<Word<N>, ParseError> {
+        if let Some(c) = row.chars().find(|c| c.is_ascii()) {
+            return Err(ParseError::InvalidFeedback(c));
+        }
+        Self::parse(guess, row)
+    }
+}
+
+// the letters separated by spaces, e.g. "S _ l u m"
+impl<const N: usize> Display for Word<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, l) in self.0.iter().enumerate() {
+            if idx > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", l)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reasons a guess and its feedback could not be turned into a `Word`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The guess or the feedback doesn't match the word length.
+    WrongLength,
+    /// A feedback character other than `M`, `C`, `H`, `?` or a result square.
+    InvalidFeedback(char),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::WrongLength => write!(f, "guess and feedback must match the word length"),
+            ParseError::InvalidFeedback(c) => {
+                write!(
+                    f,
+                    "invalid feedback character {:?}, expected M, C, H or ?, or 🟩, 🟨 or ⬛",
+                    c
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Reasons a dictionary could not be loaded into a `Wordl`, or a guess could
+/// not be applied to one.
+#[derive(Debug)]
+pub enum WordlError {
+    /// The dictionary could not be read.
+    Io(io::Error),
+    /// No usable words were found.
+    EmptyDictionary,
+    /// A word didn't have the solver's word length.
+    BadWordLength { word: String, expected: usize },
+    /// A word with a character outside the alphabet, see `Wordl::add_word`.
+    BadLetters(String),
+    /// A guess that isn't one of the allowed words, see `Wordl::guess_checked`.
+    UnknownWord(String),
+    /// Every guess has been used up, see `Wordl::set_max_guesses`.
+    OutOfGuesses { max_guesses: usize },
+    /// Feedback given for a different word than the guess, see
+    /// `MultiWordl::guess`.
+    FeedbackMismatch { guess: String, feedback: String },
+}
+
+impl Display for WordlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordlError::Io(e) => write!(f, "{}", e),
+            WordlError::EmptyDictionary => write!(f, "dictionary has no usable words"),
+            WordlError::BadWordLength { word, expected } => {
+                write!(f, "{:?} is not {} letters long", word, expected)
+            }
+            WordlError::BadLetters(word) => {
+                write!(f, "{:?} has letters outside the alphabet", word)
+            }
+            WordlError::UnknownWord(word) => write!(f, "{:?} is not an allowed word", word),
+            WordlError::OutOfGuesses { max_guesses } => {
+                write!(f, "all {} guesses have been used", max_guesses)
+            }
+            WordlError::FeedbackMismatch { guess, feedback } => {
+                write!(
+                    f,
+                    "feedback for {:?} doesn't match the guess {:?}",
+                    feedback, guess
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for WordlError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WordlError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Ways the feedback from a set of guesses can contradict itself, so that no
+/// word could satisfy it. See `Wordl::check_constraints`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Contradiction {
+    /// Two guesses put different Hits at the same position.
+    ConflictingHits {
+        position: usize,
+        first: char,
+        second: char,
+    },
+    /// A letter was marked Hit in one guess and missing altogether in another.
+    ExcludedHit(char),
+    /// A letter was marked Hit at a position where another guess said it
+    /// doesn't belong.
+    HitExcludedAt { letter: char, position: usize },
+    /// The letters known to be present need more than the word length.
+    TooManyLetters { required: usize, length: usize },
+    /// A letter must appear more often than another guess allowed.
+    CountConflict {
+        letter: char,
+        at_least: usize,
+        at_most: usize,
+    },
+}
+
+impl Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Contradiction::ConflictingHits {
+                position,
+                first,
+                second,
+            } => write!(
+                f,
+                "position {} was marked as both {:?} and {:?}",
+                position + 1,
+                first,
+                second
+            ),
+            Contradiction::ExcludedHit(c) => {
+                write!(f, "{:?} was marked as a hit but also as absent", c)
+            }
+            Contradiction::HitExcludedAt { letter, position } => write!(
+                f,
+                "{:?} was marked as a hit at position {} but also as misplaced there",
+                letter,
+                position + 1
+            ),
+            Contradiction::TooManyLetters { required, length } => write!(
+                f,
+                "{} letters are known to be present but words have only {}",
+                required, length
+            ),
+            Contradiction::CountConflict {
+                letter,
+                at_least,
+                at_most,
+            } => write!(
+                f,
+                "{:?} must appear at least {} times but at most {}",
+                letter, at_least, at_most
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Contradiction {}
+
+impl From<io::Error> for WordlError {
+    fn from(e: io::Error) -> Self {
+        WordlError::Io(e)
+    }
+}
+
+/// How often each letter occurs at one position of a list of words, see
+/// `Wordl::make_char_frequency`.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CharFreq {
+    counts: BTreeMap<char, u32>,
+    total: u32,
+}
+
+impl CharFreq {
+    fn insert(&mut self, c: char) {
+        self.counts.entry(c).and_modify(|c| *c += 1).or_insert(1);
+        self.total += 1;
+    }
+
+    /// The share of the counted letters, between 0 and 1, that are `c`; 0
+    /// when nothing was counted.
+    pub fn rate(&self, c: char) -> f64 {
+        match self.total {
+            0 => 0.0,
+            _ => *self.counts.get(&c).unwrap_or(&0) as f64 / self.total as f64,
+        }
+    }
+
+    /// The most frequent letter and its rate, the alphabetically first of
+    /// equally frequent letters, or None when nothing was counted.
+    pub fn mode(&self) -> Option<(char, f64)> {
+        let mut best: Option<(char, u32)> = None;
+        for (c, n) in &self.counts {
+            if best.is_none_or(|(_, most)| *n > most) {
+                best = Some((*c, *n));
+            }
+        }
+        best.map(|(c, _)| (c, self.rate(c)))
+    }
+}
+
+/// A snapshot of a game that can be persisted and later replayed onto a
+/// solver with the same dictionary via `Wordl::restore`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameState<const N: usize = 5> {
+    pub guesses: Vec<Word<N>>,
+    /// How many candidates remained when the snapshot was taken.
+    pub remaining: usize,
+}
+
+/// Which words the letter frequencies used for scoring are counted over, see
+/// `Wordl::set_frequency_source`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FrequencySource {
+    /// The candidates still consistent with the guesses.
+    #[default]
+    Remaining,
+    /// Every allowed word, whatever has been guessed.
+    Allowed,
+}
+
+/// How `Wordl::suggest` ranks candidates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Strategy {
+    /// Positional letter frequency.
+    #[default]
+    Frequency,
+    /// Expected information, see `Wordl::suggest_by_entropy`.
+    Entropy,
+    /// Smallest worst case, see `Wordl::suggest_minimax`.
+    Minimax,
+    /// Distinct letter coverage, see `Wordl::suggest_by_letter_coverage`.
+    LetterCoverage,
+    /// Real-world commonness, see `Wordl::suggest_by_commonness`.
+    Commonness,
+}
+
+/// How a word was scored, see `Wordl::explain`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreBreakdown {
+    /// The strategy that scored the word, `None` for a custom `Scorer`.
+    pub strategy: Option<Strategy>,
+    /// The score the strategy ranks by, the sum of `components`. Minimax
+    /// ranks lower scores first, every other strategy higher.
+    pub score: f64,
+    /// Named parts of the score: a letter for each unsolved position
+    /// (`Frequency`), a feedback pattern for each group of remaining words
+    /// (`Entropy`), the largest group (`Minimax`), each distinct letter
+    /// (`LetterCoverage`) or the word itself (`Commonness`).
+    pub components: Vec<(String, f64)>,
+    /// How many remaining words would get each feedback, for `Entropy` and
+    /// `Minimax`.
+    pub buckets: BTreeMap<String, usize>,
+}
+
+/// A suggested word with its score, see `Wordl::suggest_detailed`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Suggestion {
+    pub word: String,
+    /// The current `Scorer`'s score for the word.
+    pub score: f64,
+    /// Whether the word could still be the answer, rather than only a probe.
+    pub still_possible: bool,
+}
+
+/// Solver state: the words still in play and the guesses made so far, for
+/// words of length `N`. The dictionary itself is never narrowed; guesses
+/// filter a cached set of candidates that `undo` and `reset` rebuild from it.
+#[derive(Clone)]
+pub struct Wordl<const N: usize = 5> {
+    // every word that may be guessed, the source of probes for `suggest_any`
+    // and friends
+    allowed: BTreeSet<String>,
+    // every possible answer, before any guesses
+    dictionary: BTreeSet<String>,
+    // the possible answers still consistent with the guesses
+    answers: BTreeSet<String>,
+    guesses: Vec<Word<N>>,
+    // bits the last `guess` revealed, see `last_information_gain`
+    last_gain: Option<f64>,
+    // letters known to be present apart from any guess, see `require_letter`
+    required: BTreeSet<char>,
+    hard_mode: bool,
+    // how many guesses a game allows, see `is_lost`
+    max_guesses: usize,
+    // seeds the sampling in `suggest_by_entropy_sampled`, random when unset
+    seed: Option<u64>,
+    strategy: Strategy,
+    // set by `set_scorer`, used in place of `strategy`
+    scorer: Option<Arc<dyn Scorer<N>>>,
+    // the letters words may be made of, any alphabetic character when unset
+    alphabet: Option<BTreeSet<char>>,
+    // how much repeated letters count towards the positional score
+    duplicate_weight: f64,
+    // weight of the untried-placement term in `suggest_weighted`
+    placement_bonus: f64,
+    // the candidate count at or below which `suggest_adaptive` stops probing
+    adaptive_threshold: usize,
+    // how common each word is in real-world use, for `suggest_by_commonness`
+    commonness: BTreeMap<String, f64>,
+    // positional letter frequency of `answers`, kept in step with it
+    freq: [CharFreq; N],
+    // positional letter frequency of `allowed`, built on first use
+    allowed_freq: OnceLock<[CharFreq; N]>,
+    frequency_source: FrequencySource,
+    // the answer to `best_first_guess`, cleared whenever the words change
+    first_guess: OnceLock<String>,
+    // the answer to `opening_book`, cleared along with `first_guess`
+    book: OnceLock<OpeningBook>,
+}
+
+impl<const N: usize> Default for Wordl<N> {
+    fn default() -> Self {
+        Wordl {
+            allowed: BTreeSet::default(),
+            dictionary: BTreeSet::default(),
+            answers: BTreeSet::default(),
+            guesses: Vec::default(),
+            last_gain: None,
+            required: BTreeSet::default(),
+            hard_mode: false,
+            max_guesses: 6,
+            seed: None,
+            strategy: Strategy::default(),
+            scorer: None,
+            alphabet: None,
+            duplicate_weight: 1.0,
+            placement_bonus: 0.0,
+            adaptive_threshold: 3,
+            commonness: BTreeMap::default(),
+            freq: std::array::from_fn(|_| CharFreq::default()),
+            allowed_freq: OnceLock::new(),
+            frequency_source: FrequencySource::default(),
+            first_guess: OnceLock::new(),
+            book: OnceLock::new(),
+        }
+    }
+}
+
+impl<const N: usize> Debug for Wordl<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Wordl")
+            .field("guesses", &self.guesses)
+            .field("hard_mode", &self.hard_mode)
+            .field("max_guesses", &self.max_guesses)
+            .field("seed", &self.seed)
+            .field("strategy", &self.strategy)
+            .field("custom_scorer", &self.scorer.is_some())
+            .field("duplicate_weight", &self.duplicate_weight)
+            .field("placement_bonus", &self.placement_bonus)
+            .finish()
+    }
+}
+
+// `for word in &w` iterates the remaining candidates, as `remaining` does
+impl<'a, const N: usize> IntoIterator for &'a Wordl<N> {
+    type Item = &'a str;
+    type IntoIter = std::iter::Map<btree_set::Iter<'a, String>, fn(&'a String) -> &'a str>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.answers.iter().map(String::as_str)
+    }
+}
+
+// pairs each probe with its score, keeping the order of `probes`. Scoring
+// runs in parallel with the `rayon` feature.
+fn score_all<'a, I, T, F>(probes: I, score: F) -> Vec<(T, &'a String)>
+where
+    I: IntoIterator<Item = &'a String>,
+    T: Send,
+    F: Fn(&String) -> T + Sync,
+{
+    let probes: Vec<&String> = probes.into_iter().collect();
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        probes.into_par_iter().map(|p| (score(p), p)).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        probes.into_iter().map(|p| (score(p), p)).collect()
+    }
+}
+
+// SplitMix64, a small fast generator that is plenty for picking samples
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // a number in `0..bound`, with a bias too small to matter here
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
+
+// the letters of `s` with their positions, leaving out the `skip` positions
+// and any past the end of `skip`
+fn open_letters<'a>(s: &'a str, skip: &'a [bool]) -> impl Iterator<Item = (usize, char)> + 'a {
+    s.chars()
+        .enumerate()
+        .filter(|(idx, _)| !skip.get(*idx).copied().unwrap_or(true))
+}
+
+// sorts scored probes highest first and returns the best `upto` of them.
+// Equal scores are ordered alphabetically so results are reproducible.
+fn take_highest(mut scored: Vec<(f64, &String)>, upto: usize) -> Vec<String> {
+    scored.sort_by(|a, b| {
+        b.0.partial_cmp(&a.0)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.1.cmp(b.1))
+    });
+    scored
+        .into_iter()
+        .take(upto)
+        .map(|(_, w)| w.clone())
+        .collect()
+}
+
+// a scored probe in a `BinaryHeap`, which pops them by `key` highest first,
+// then words that could still be the answer, then alphabetically. `score` is
+// the scorer's own score, reported alongside.
+struct Ranked<'a> {
+    key: f64,
+    score: f64,
+    possible: bool,
+    word: &'a String,
+}
+
+impl Ord for Ranked<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key
+            .partial_cmp(&other.key)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.possible.cmp(&other.possible))
+            .then_with(|| other.word.cmp(self.word))
+    }
+}
+
+impl PartialOrd for Ranked<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Ranked<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ranked<'_> {}
+
+// the bits of information in going from `before` candidates to `after`
+fn information_gain(before: usize, after: usize) -> Option<f64> {
+    (after > 0).then(|| (before as f64 / after as f64).log2())
+}
+
+// quotes `s` as a JSON string
+fn json_string(s: &str) -> String {
+    let mut result = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+// true when `s` has at least the required number of each letter
+fn has_min_counts(s: &str, min_counts: &BTreeMap<char, usize>) -> bool {
+    min_counts
+        .iter()
+        .all(|(c, min)| s.chars().filter(|cc| cc == c).count() >= *min)
+}
+
+// https://github.com/charlesreid1/five-letter-words/blob/master/sgb-words.txt
+const DEFAULT_DICTIONARY: &str = include_str!("../words.txt");
+// `best_first_guess` for `DEFAULT_DICTIONARY`, see the ignored
+// `default_first_guess_is_current` test
+const DEFAULT_FIRST_GUESS: &str = "tares";
+
+impl Wordl {
+    /// Creates an empty solver for five-letter words. Other lengths are built
+    /// with `Wordl::<N>::default()`.
+    pub fn new() -> Self {
+        Wordl::default()
+    }
+
+    /// Creates a solver loaded with the embedded SGB five-letter word list.
+    /// Its `best_first_guess` is known ahead of time.
+    pub fn with_default_dictionary() -> Self {
+        let mut w = Wordl::new();
+        for word in DEFAULT_DICTIONARY.lines() {
+            w.insert(word.to_string());
+        }
+        w.set_best_first_guess(DEFAULT_FIRST_GUESS.to_string());
+        w
+    }
+}
+
+impl<const N: usize> Wordl<N> {
+    /// Loads a dictionary of one word per line from `path`, skipping lines
+    /// that `insert` rejects.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, WordlError> {
+        Self::load_from(BufReader::new(File::open(path)?))
+    }
+
+    /// Loads a dictionary of one word per line from any reader, e.g. a
+    /// string, stdin or a decompression stream. Lines are sanitized as in
+    /// `insert`.
+    pub fn load_from<R: BufRead>(reader: R) -> Result<Self, WordlError> {
+        let mut w = Wordl::default();
+        for line in reader.lines() {
+            w.insert(line?);
+        }
+        if w.remaining_count() == 0 {
+            return Err(WordlError::EmptyDictionary);
+        }
+        Ok(w)
+    }
+
+    /// Creates a solver whose answers and allowed words are `words`,
+    /// sanitized as in `insert`.
+    pub fn from_words<I: IntoIterator<Item = String>>(words: I) -> Self {
+        let mut w = Wordl::default();
+        for word in words {
+            w.insert(word);
+        }
+        w
+    }
+
+    /// The number of letters in each word, `N`.
+    pub fn len_of_word(&self) -> usize {
+        N
+    }
+
+    /// Starts configuring a solver, see `WordlBuilder`.
+    pub fn builder() -> WordlBuilder<N> {
+        WordlBuilder::default()
+    }
+
+    /// Creates a solver whose possible answers are `answers` and which may
+    /// additionally probe with any of `allowed`. Every answer is allowed.
+    pub fn from_lists<A, B>(answers: A, allowed: B) -> Self
+    where
+        A: IntoIterator<Item = String>,
+        B: IntoIterator<Item = String>,
+    {
+        let mut w = Wordl::default();
+        for word in answers {
+            w.insert(word);
+        }
+        for word in allowed {
+            w.insert_allowed(word);
+        }
+        w
+    }
+
+    /// Adds a word as both a possible answer and an allowed guess. Surrounding
+    /// whitespace is trimmed and the word is stored lowercase; it is rejected,
+    /// returning false, unless it is then exactly `N` characters of the
+    /// alphabet, see `set_alphabet`.
+    pub fn insert(&mut self, word: String) -> bool {
+        match self.sanitize(&word) {
+            Some(word) => {
+                let allowed = self.allowed.insert(word.clone());
+                let added = self.dictionary.insert(word.clone());
+                if added || allowed {
+                    self.first_guess = OnceLock::new();
+                    self.book = OnceLock::new();
+                    self.allowed_freq = OnceLock::new();
+                }
+                // a word added mid-game is only a candidate if it fits the
+                // guesses so far
+                let fits = self.filter(&self.guesses).is_valid(&word);
+                if added && fits {
+                    for (idx, c) in word.chars().enumerate() {
+                        self.freq[idx].insert(c);
+                    }
+                    self.answers.insert(word);
+                }
+                added
+            }
+            None => false,
+        }
+    }
+
+    /// Like `insert`, but says why a word was turned away. Adding a word
+    /// that is already in the dictionary succeeds.
+    pub fn add_word(&mut self, word: &str) -> Result<(), WordlError> {
+        if self.sanitize(word).is_none() {
+            let trimmed = word.trim().to_string();
+            if trimmed.chars().count() != N {
+                return Err(WordlError::BadWordLength {
+                    word: trimmed,
+                    expected: N,
+                });
+            }
+            return Err(WordlError::BadLetters(trimmed));
+        }
+        self.insert(word.to_string());
+        Ok(())
+    }
+
+    /// Removes every word, keeping the guesses and settings, so a word list
+    /// can be built up again with `insert` or `add_word`.
+    pub fn clear_dictionary(&mut self) {
+        self.allowed.clear();
+        self.dictionary.clear();
+        self.answers.clear();
+        self.freq = std::array::from_fn(|_| CharFreq::default());
+        self.first_guess = OnceLock::new();
+        self.book = OnceLock::new();
+        self.allowed_freq = OnceLock::new();
+    }
+
+    /// Adds a word that may be guessed but is never the answer, with the same
+    /// sanitization as `insert`.
+    pub fn insert_allowed(&mut self, word: String) -> bool {
+        match self.sanitize(&word) {
+            Some(word) => {
+                let added = self.allowed.insert(word);
+                if added {
+                    self.first_guess = OnceLock::new();
+                    self.book = OnceLock::new();
+                    self.allowed_freq = OnceLock::new();
+                }
+                added
+            }
+            None => false,
+        }
+    }
+
+    fn sanitize(&self, word: &str) -> Option<String> {
+        let word = word.trim().to_lowercase();
+        if word.chars().count() != N || !word.chars().all(|c| self.in_alphabet(c)) {
+            return None;
+        }
+        Some(word)
+    }
+
+    fn in_alphabet(&self, c: char) -> bool {
+        match &self.alphabet {
+            Some(alphabet) => alphabet.contains(&c),
+            None => c.is_alphabetic(),
+        }
+    }
+
+    /// Restricts words to the given letters, e.g. to add `ñ` for Spanish
+    /// puzzles or to keep accented words out of an English list. Letters are
+    /// compared lowercase. Words already loaded that fall outside the alphabet
+    /// are dropped; by default any alphabetic character is accepted.
+    pub fn set_alphabet<I: IntoIterator<Item = char>>(&mut self, alphabet: I) {
+        let alphabet: BTreeSet<char> = alphabet.into_iter().flat_map(char::to_lowercase).collect();
+        let fits = |word: &String| word.chars().all(|c| alphabet.contains(&c));
+        // every answer is also allowed, so this notices any word dropped
+        let before = self.allowed.len();
+        self.allowed.retain(fits);
+        self.dictionary.retain(fits);
+        self.answers.retain(fits);
+        if self.allowed.len() != before {
+            self.first_guess = OnceLock::new();
+            self.book = OnceLock::new();
+            self.allowed_freq = OnceLock::new();
+        }
+        self.alphabet = Some(alphabet);
+        self.freq = Self::make_char_frequency(self.answers.iter());
+    }
+
+    /// The words guessed so far, in order.
+    pub fn guessed_words(&self) -> Vec<String> {
+        self.guesses.iter().map(Word::letters).collect()
+    }
+
+    /// Captures the guesses made so far along with the number of remaining
+    /// candidates.
+    pub fn state(&self) -> GameState<N> {
+        GameState {
+            guesses: self.guesses.clone(),
+            remaining: self.answers.len(),
+        }
+    }
+
+    /// Replays the guesses of a saved game, which assumes the dictionary was
+    /// loaded the same way as when `state` was called.
+    pub fn restore(&mut self, state: GameState<N>) {
+        self.apply_guesses(state.guesses);
+    }
+
+    /// The guesses so far as a JSON array with one object per guess, giving
+    /// the guessed word, its `M`/`C`/`H` feedback and how many candidates
+    /// were left after it:
+    ///
+    /// ```json
+    /// [{"guess":"crane","feedback":"MMMMM","remaining_after":738}]
+    /// ```
+    pub fn game_log_json(&self) -> String {
+        let entries: Vec<String> = (1..=self.guesses.len())
+            .map(|n| {
+                let remaining = self.valid_answers(&self.guesses[..n]).len();
+                let word = &self.guesses[n - 1];
+                format!(
+                    "{{\"guess\":{},\"feedback\":\"{}\",\"remaining_after\":{}}}",
+                    json_string(&word.letters()),
+                    word.feedback(),
+                    remaining
+                )
+            })
+            .collect();
+        format!("[{}]", entries.join(","))
+    }
+
+    /// The guesses so far as a shareable result grid, headed `Wordl 3/6` with
+    /// the number of guesses once solved and `Wordl X/6` otherwise, out of
+    /// `max_guesses`.
+    pub fn share_grid(&self) -> String {
+        let solved = self
+            .guesses
+            .last()
+            .is_some_and(|word| word.0.iter().all(|l| matches!(l, Letter::Hit(_))));
+        let score = if solved {
+            self.guesses.len().to_string()
+        } else {
+            "X".to_string()
+        };
+        let mut result = format!("Wordl {}/{}\n", score, self.max_guesses);
+        for word in &self.guesses {
+            result.push('\n');
+            result.push_str(&word.emoji_row());
+        }
+        result
+    }
+
+    /// Turns hard mode on or off. In hard mode `suggest_any` only proposes
+    /// words that reuse every Hit in place and include every Contains letter.
+    pub fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.hard_mode = hard_mode;
+    }
+
+    pub fn hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+
+    /// Sets how many guesses a game allows, 6 by default. `guess_checked`
+    /// refuses guesses past the limit; `guess` records them regardless.
+    pub fn set_max_guesses(&mut self, max_guesses: usize) {
+        self.max_guesses = max_guesses;
+    }
+
+    pub fn max_guesses(&self) -> usize {
+        self.max_guesses
+    }
+
+    /// How many more guesses the limit allows.
+    pub fn guesses_remaining(&self) -> usize {
+        self.max_guesses.saturating_sub(self.guesses.len())
+    }
+
+    /// Sets how much the second and later occurrences of a letter count
+    /// towards the positional score, from 1 (the default, no penalty) down to
+    /// 0 (ignored). Penalizing repeats favours openers that test more letters.
+    pub fn set_duplicate_weight(&mut self, weight: f64) {
+        self.duplicate_weight = weight.clamp(0.0, 1.0);
+    }
+
+    /// Sets how much `suggest_weighted` rewards a word for each letter known
+    /// to be present that it tries somewhere new, a position where the letter
+    /// was never marked Miss or Contains. 0, the default, turns this off.
+    pub fn set_placement_bonus(&mut self, bonus: f64) {
+        self.placement_bonus = bonus.max(0.0);
+    }
+
+    /// Chooses which words the letter-frequency scoring (`Strategy::Frequency`,
+    /// `Strategy::LetterCoverage` and `suggest_weighted`) counts letters
+    /// over. The remaining candidates, the default, say most about the answer
+    /// once a few guesses are in; every allowed word gives rankings that don't
+    /// shift as the candidates narrow.
+    pub fn set_frequency_source(&mut self, source: FrequencySource) {
+        self.frequency_source = source;
+    }
+
+    pub fn frequency_source(&self) -> FrequencySource {
+        self.frequency_source
+    }
+
+    // the positional letter frequency scoring counts with
+    fn scoring_freq(&self) -> &[CharFreq; N] {
+        match self.frequency_source {
+            FrequencySource::Remaining => &self.freq,
+            FrequencySource::Allowed => self
+                .allowed_freq
+                .get_or_init(|| Self::make_char_frequency(self.allowed.iter())),
+        }
+    }
+
+    /// Chooses how `suggest` ranks candidates, replacing any custom scorer.
+    pub fn set_strategy(&mut self, strategy: Strategy) {
+        self.strategy = strategy;
+        self.scorer = None;
+    }
+
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// Ranks suggestions with a custom `Scorer` until `set_strategy` is called.
+    pub fn set_scorer<S: Scorer<N> + 'static>(&mut self, scorer: S) {
+        self.scorer = Some(Arc::new(scorer));
+    }
+
+    // the custom scorer if there is one, otherwise the strategy
+    fn scorer(&self) -> &dyn Scorer<N> {
+        match &self.scorer {
+            Some(scorer) => scorer.as_ref(),
+            None => &self.strategy,
+        }
+    }
+
+    // the scorer for ranking probes: as `scorer`, except that positional
+    // frequency counts the solved positions too, as probes differ there
+    fn probe_scorer(&self) -> &dyn Scorer<N> {
+        match (&self.scorer, self.strategy) {
+            (None, Strategy::Frequency) => &ProbeFrequency,
+            _ => self.scorer(),
+        }
+    }
+
+    /// Returns up to `upto` candidates ranked by the current `Scorer`,
+    /// positional letter frequency by default. Only words that could still be
+    /// the answer are suggested, so every suggestion might win outright; see
+    /// `suggest_from_allowed` for probes that only narrow things down.
+    pub fn suggest(&self, upto: usize) -> Vec<String> {
+        log::debug!(
+            "suggest {} of {} candidates with {}",
+            upto,
+            self.answers.len(),
+            self.scorer_name()
+        );
+        let result = self.rank_with(self.scorer(), &self.answers, upto);
+        log::trace!("suggestions {:?}", result);
+        result
+    }
+
+    /// The candidates starting with `prefix`, e.g. for autocompletion, in the
+    /// order `suggest` ranks them. An empty prefix matches every candidate.
+    pub fn answers_matching(&self, prefix: &str) -> Vec<String> {
+        let prefix = prefix.trim().to_lowercase();
+        let matching = self.answers.iter().filter(|w| w.starts_with(&prefix));
+        self.rank_with(self.scorer(), matching, usize::MAX)
+    }
+
+    /// Like `suggest`, but with each word's score.
+    pub fn suggest_detailed(&self, upto: usize) -> Vec<Suggestion> {
+        self.detailed(self.suggest_iter().take(upto))
+    }
+
+    /// Ranks every allowed word with the current `Scorer`, as
+    /// `suggest_from_allowed` does for a custom one, along with each word's
+    /// score and whether it could still be the answer.
+    pub fn suggest_any_detailed(&self, upto: usize) -> Vec<Suggestion> {
+        self.detailed(
+            self.rank_iter(self.probe_scorer(), self.probes())
+                .take(upto),
+        )
+    }
+
+    fn detailed<I: Iterator<Item = (String, f64)>>(&self, ranked: I) -> Vec<Suggestion> {
+        ranked
+            .map(|(word, score)| Suggestion {
+                still_possible: self.answers.contains(&word),
+                word,
+                score,
+            })
+            .collect()
+    }
+
+    /// Iterates every candidate with its score, in the order `suggest` ranks
+    /// them. Words are scored up front but only ordered as they are taken, so
+    /// taking a few is cheaper than `suggest` with a large `upto`.
+    pub fn suggest_iter(&self) -> impl Iterator<Item = (String, f64)> + '_ {
+        self.rank_iter(self.scorer(), &self.answers)
+    }
+
+    // names the scorer for logging
+    fn scorer_name(&self) -> String {
+        match &self.scorer {
+            Some(_) => "a custom scorer".to_string(),
+            None => format!("{:?}", self.strategy),
+        }
+    }
+
+    fn rank_with<'a, I>(&self, scorer: &dyn Scorer<N>, probes: I, upto: usize) -> Vec<String>
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        self.rank_iter(scorer, probes)
+            .take(upto)
+            .map(|(word, _)| word)
+            .collect()
+    }
+
+    fn rank_iter<'a, I>(
+        &self,
+        scorer: &dyn Scorer<N>,
+        probes: I,
+    ) -> impl Iterator<Item = (String, f64)> + 'a
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        let sign = if scorer.higher_is_better() { 1.0 } else { -1.0 };
+        // words of the wrong length can't be played, leave them out
+        let probes = probes.into_iter().filter(|s| s.chars().count() == N);
+        let scored = score_all(probes, |s: &String| scorer.score(s, self));
+        let mut heap: BinaryHeap<Ranked> = scored
+            .into_iter()
+            .map(|(score, word)| Ranked {
+                key: sign * score,
+                score,
+                possible: self.answers.contains(word),
+                word,
+            })
+            .collect();
+        std::iter::from_fn(move || heap.pop().map(|r| (r.word.clone(), r.score)))
+    }
+
+    /// Like `suggest`, but ranks every allowed word, including words that are
+    /// already ruled out or can never be the answer. Such a probe can't win
+    /// this turn but may split the remaining candidates better than any of
+    /// them. In hard mode probes must still respect the revealed clues.
+    pub fn suggest_from_allowed(&self, upto: usize) -> Vec<String> {
+        log::debug!(
+            "suggest {} of {} allowed words with {}",
+            upto,
+            self.allowed.len(),
+            self.scorer_name()
+        );
+        self.rank_with(self.probe_scorer(), self.probes(), upto)
+    }
+
+    /// Returns up to `upto` probe words drawn from every allowed word, even
+    /// those that can't be the answer, ranked by the positional letter
+    /// frequency of the remaining candidates. In hard mode probes must still
+    /// respect the revealed clues.
+    pub fn suggest_any(&self, upto: usize) -> Vec<String> {
+        self.rank_with(&ProbeFrequency, self.probes(), upto)
+    }
+
+    // positions whose letter is already known from a Hit. Every remaining
+    // candidate agrees there, so those positions are left out when ranking
+    // candidates against each other.
+    fn solved_positions(&self) -> [bool; N] {
+        Self::make_hits(&self.guesses).map(|hit| hit.is_some())
+    }
+
+    // the allowed words `suggest_any` and friends may propose
+    fn probes(&self) -> Vec<&String> {
+        if self.hard_mode {
+            let allowed = Self::make_is_hard_mode_valid(&self.guesses);
+            self.allowed.iter().filter(|w| allowed(w)).collect()
+        } else {
+            self.allowed.iter().collect()
+        }
+    }
+
+    // sum of how often each letter appears at its position among the
+    // remaining candidates, repeated letters weighted by `duplicate_weight`
+    // and `skip` positions left out
+    fn positional_score(&self, s: &str, skip: &[bool; N]) -> f64 {
+        self.positional_parts(s, skip).map(|(_, part)| part).sum()
+    }
+
+    // each letter's share of `positional_score`
+    fn positional_parts<'a>(
+        &'a self,
+        s: &'a str,
+        skip: &'a [bool; N],
+    ) -> impl Iterator<Item = (char, f64)> + 'a {
+        let mut seen = BTreeSet::new();
+        open_letters(s, skip).map(move |(idx, c)| {
+            let weight = if seen.insert(c) {
+                1.0
+            } else {
+                self.duplicate_weight
+            };
+            (c, weight * self.scoring_freq()[idx].rate(c))
+        })
+    }
+
+    /// Returns up to `upto` candidates ranked by a blend of positional and
+    /// global letter frequency:
+    ///
+    /// `alpha * Σ positional(idx, c) + (1 - alpha) * Σ global(c)`
+    ///
+    /// where `positional(idx, c)` is the share of remaining candidates with
+    /// `c` at `idx` and `global(c)` is the share of all their letters that are
+    /// `c`. `alpha` is clamped to `[0, 1]`; 1 ranks like `suggest` and 0 by
+    /// global frequency alone. With `set_placement_bonus` each word also
+    /// earns the bonus for every known letter it moves to an untried position.
+    pub fn suggest_weighted(&self, upto: usize, alpha: f64) -> Vec<String> {
+        self.rank_weighted(&self.answers, &self.solved_positions(), alpha, upto)
+    }
+
+    /// Like `suggest_weighted` but ranks every allowed word, the way
+    /// `suggest_any` does, which is where the placement bonus matters most:
+    /// the remaining candidates already avoid every tried position.
+    pub fn suggest_any_weighted(&self, upto: usize, alpha: f64) -> Vec<String> {
+        self.rank_weighted(self.probes(), &[false; N], alpha, upto)
+    }
+
+    // `skip` are the positions left out, the solved ones for candidates
+    fn rank_weighted<'a, I>(
+        &self,
+        probes: I,
+        skip: &[bool; N],
+        alpha: f64,
+        upto: usize,
+    ) -> Vec<String>
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        let alpha = alpha.clamp(0.0, 1.0);
+        let global = self.letter_frequency(skip);
+        let tried = Self::make_tried_at(&self.guesses);
+        let known: BTreeSet<char> = Self::make_excludes_at(&self.guesses)
+            .into_iter()
+            .flatten()
+            .collect();
+        let scored = score_all(probes, |s: &String| {
+            let global_score: f64 = open_letters(s, skip).map(|(_, c)| global.rate(c)).sum();
+            let placements = known
+                .iter()
+                .filter(|k| {
+                    s.chars()
+                        .enumerate()
+                        .any(|(idx, c)| c == **k && tried.get(idx).is_some_and(|t| !t.contains(k)))
+                })
+                .count();
+            alpha * self.positional_score(s, skip)
+                + (1.0 - alpha) * global_score
+                + self.placement_bonus * placements as f64
+        });
+        take_highest(scored, upto)
+    }
+
+    /// Records how common `word` is, e.g. its count in a corpus. Only the
+    /// relative values matter.
+    pub fn insert_commonness(&mut self, word: &str, frequency: f64) {
+        self.commonness
+            .insert(word.trim().to_lowercase(), frequency);
+    }
+
+    /// Reads word commonness from tab separated `word<TAB>frequency` lines,
+    /// skipping lines that don't parse.
+    pub fn load_commonness<R: BufRead>(&mut self, reader: R) -> Result<(), WordlError> {
+        for line in reader.lines() {
+            let line = line?;
+            let mut fields = line.split('\t');
+            if let (Some(word), Some(Ok(frequency))) =
+                (fields.next(), fields.next().map(|f| f.trim().parse()))
+            {
+                self.insert_commonness(word, frequency);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns up to `upto` candidates ranked by how common they are, most
+    /// common first. Words with no recorded commonness rank as least common.
+    pub fn suggest_by_commonness(&self, upto: usize) -> Vec<String> {
+        self.rank_with(&Strategy::Commonness, &self.answers, upto)
+    }
+
+    /// Probes for information while many candidates remain, then goes for
+    /// the answer: `suggest_any_by_entropy` while more than the adaptive
+    /// threshold (3 by default) remain, `suggest_by_commonness` after.
+    pub fn suggest_adaptive(&self, upto: usize) -> Vec<String> {
+        if self.remaining_count() > self.adaptive_threshold {
+            self.suggest_any_by_entropy(upto)
+        } else {
+            self.suggest_by_commonness(upto)
+        }
+    }
+
+    /// Sets how few candidates must remain before `suggest_adaptive` stops
+    /// probing and ranks the candidates by commonness.
+    pub fn set_adaptive_threshold(&mut self, threshold: usize) {
+        self.adaptive_threshold = threshold;
+    }
+
+    // the recorded commonness of `word`, 0 when there is none
+    fn commonness_of(&self, word: &str) -> f64 {
+        self.commonness.get(word).copied().unwrap_or(0.0)
+    }
+
+    /// Returns up to `upto` candidates ranked by the summed frequency, over
+    /// every position of the remaining candidates, of their distinct letters.
+    /// Repeated letters only count once, which favours words that cover many
+    /// common letters and makes for good opening guesses.
+    pub fn suggest_by_letter_coverage(&self, upto: usize) -> Vec<String> {
+        self.rank_with(&Strategy::LetterCoverage, &self.answers, upto)
+    }
+
+    // the frequency of each distinct letter of `s` outside the solved
+    // positions, the parts of its letter coverage score
+    fn coverage_parts(&self, s: &str) -> Vec<(char, f64)> {
+        let skip = self.solved_positions();
+        let freq = self.letter_frequency(&skip);
+        let letters: BTreeSet<char> = open_letters(s, &skip).map(|(_, c)| c).collect();
+        letters.into_iter().map(|c| (c, freq.rate(c))).collect()
+    }
+
+    /// For each position, the share of remaining candidates with each letter
+    /// there. Every position's shares add up to 1 while candidates remain.
+    pub fn position_letter_probabilities(&self) -> [BTreeMap<char, f64>; N] {
+        std::array::from_fn(|idx| {
+            let position = &self.freq[idx];
+            position
+                .counts
+                .keys()
+                .map(|c| (*c, position.rate(*c)))
+                .collect()
+        })
+    }
+
+    /// For each position, the letter most of the remaining candidates have
+    /// there and its share of them, or `None` once no candidates remain.
+    pub fn most_likely_letters(&self) -> [Option<(char, f64)>; N] {
+        std::array::from_fn(|idx| self.freq[idx].mode())
+    }
+
+    /// The letter known at each position, the green squares.
+    pub fn known_hits(&self) -> [Option<char>; N] {
+        Self::make_hits(&self.guesses)
+    }
+
+    /// The letters known to be in the answer whose position isn't known yet,
+    /// the yellow squares, in alphabetical order. A letter appears once for
+    /// each such occurrence, e.g. twice after a guess with two yellow 'e's.
+    /// Letters added with `require_letter` count as yellow until placed.
+    pub fn known_contains(&self) -> Vec<char> {
+        let hits = self.known_hits();
+        self.min_counts(&self.guesses)
+            .into_iter()
+            .flat_map(|(c, n)| {
+                let placed = hits.iter().filter(|h| **h == Some(c)).count();
+                std::iter::repeat_n(c, n.saturating_sub(placed))
+            })
+            .collect()
+    }
+
+    /// The letters ruled out of the answer entirely, the greyed-out keys.
+    pub fn excluded_letters(&self) -> BTreeSet<char> {
+        Self::make_excludes(&self.guesses)
+    }
+
+    /// The positions, from 0 and in order, where `c` has been marked
+    /// Contains, so the answer doesn't have it there.
+    pub fn contains_letter_positions(&self, c: char) -> Vec<usize> {
+        let lower: Vec<char> = c.to_lowercase().collect();
+        (0..N)
+            .filter(|idx| {
+                self.guesses
+                    .iter()
+                    .any(|word| matches!(word.0[*idx], Letter::Contains(l) if lower.contains(&l)))
+            })
+            .collect()
+    }
+
+    /// The letters not yet known to be in the answer or ruled out, the ones
+    /// still worth trying. Drawn from the alphabet, or from the letters of
+    /// the allowed words when no alphabet is set.
+    pub fn unknown_letters(&self) -> BTreeSet<char> {
+        let mut result: BTreeSet<char> = match &self.alphabet {
+            Some(alphabet) => alphabet.clone(),
+            None => self.allowed.iter().flat_map(|w| w.chars()).collect(),
+        };
+        for c in Self::make_excludes(&self.guesses) {
+            result.remove(&c);
+        }
+        for c in self.min_counts(&self.guesses).keys() {
+            result.remove(c);
+        }
+        result
+    }
+
+    /// Returns up to `upto` opening words from the dictionary, ranked by how
+    /// common their distinct letters are across the whole dictionary. Unlike
+    /// `suggest` this ignores letter positions and any guesses made, and
+    /// favours words made only of common letters, like "arose".
+    pub fn suggest_opener(&self, upto: usize) -> Vec<String> {
+        let mut freq = CharFreq::default();
+        for c in self.dictionary.iter().flat_map(|word| word.chars()) {
+            freq.insert(c);
+        }
+        let scored = score_all(&self.dictionary, |s: &String| {
+            let distinct: BTreeSet<char> = s.chars().collect();
+            distinct.into_iter().map(|c| freq.rate(c)).sum::<f64>()
+        });
+        take_highest(scored, upto)
+    }
+
+    // letter frequency of the frequency source regardless of position,
+    // leaving out the `skip` positions
+    fn letter_frequency(&self, skip: &[bool; N]) -> CharFreq {
+        let mut result = CharFreq::default();
+        for (idx, position) in self.scoring_freq().iter().enumerate() {
+            if skip[idx] {
+                continue;
+            }
+            for (c, n) in &position.counts {
+                *result.counts.entry(*c).or_insert(0) += n;
+            }
+            result.total += position.total;
+        }
+        result
+    }
+
+    /// Returns the number of words still consistent with the guesses so far.
+    pub fn remaining_count(&self) -> usize {
+        self.answers.len()
+    }
+
+    /// The number of possible answers before any guesses.
+    pub fn original_size(&self) -> usize {
+        self.dictionary.len()
+    }
+
+    /// The share of the possible answers ruled out so far, from 0 before any
+    /// guesses towards 1 as the answer is narrowed down.
+    pub fn reduction_ratio(&self) -> f64 {
+        match self.original_size() {
+            0 => 0.0,
+            total => 1.0 - self.remaining_count() as f64 / total as f64,
+        }
+    }
+
+    /// Returns every word still consistent with the guesses so far, in
+    /// alphabetical order. `suggest(remaining_count())` gives the same words
+    /// ranked.
+    pub fn candidates(&self) -> Vec<String> {
+        self.answers.iter().cloned().collect()
+    }
+
+    /// Iterates every possible answer, whatever has been guessed, in
+    /// alphabetical order.
+    pub fn dictionary(&self) -> impl Iterator<Item = &str> {
+        self.dictionary.iter().map(String::as_str)
+    }
+
+    /// Iterates the words still consistent with the guesses so far, in
+    /// alphabetical order.
+    pub fn remaining(&self) -> impl Iterator<Item = &str> {
+        self.answers.iter().map(String::as_str)
+    }
+
+    /// Returns true once the answer is known: either a single candidate is
+    /// left or the last guess was all Hits.
+    pub fn is_solved(&self) -> bool {
+        let all_hits = self
+            .guesses
+            .last()
+            .is_some_and(|word| word.0.iter().all(|l| matches!(l, Letter::Hit(_))));
+        all_hits || self.answers.len() == 1
+    }
+
+    /// The answer, once it is the only candidate left.
+    pub fn solution(&self) -> Option<String> {
+        match self.answers.len() {
+            1 => self.answers.first().cloned(),
+            _ => None,
+        }
+    }
+
+    /// Returns true once every guess is used up without solving the puzzle.
+    pub fn is_lost(&self) -> bool {
+        self.guesses.len() >= self.max_guesses && !self.is_solved()
+    }
+
+    /// Returns `suggest(upto)` along with `remaining_count()`.
+    pub fn suggest_with_count(&self, upto: usize) -> (Vec<String>, usize) {
+        (self.suggest(upto), self.remaining_count())
+    }
+
+    /// Returns `limit` candidates starting at `offset`, in the order `suggest`
+    /// ranks them, along with `remaining_count()`. Pages past the end are
+    /// empty.
+    pub fn candidates_page(&self, offset: usize, limit: usize) -> (Vec<String>, usize) {
+        let ranked = self.suggest(offset.saturating_add(limit));
+        let page = ranked.into_iter().skip(offset).collect();
+        (page, self.remaining_count())
+    }
+
+    /// Returns up to `upto` candidates ranked by the expected information, in
+    /// bits, of the feedback they would get against the remaining words.
+    pub fn suggest_by_entropy(&self, upto: usize) -> Vec<String> {
+        self.rank_with(&Strategy::Entropy, &self.answers, upto)
+    }
+
+    /// Like `suggest_by_entropy`, but estimates the information of each
+    /// candidate against a random sample of at most `sample` remaining words
+    /// instead of all of them, which is much faster when many remain. The
+    /// sample is drawn from `set_seed`'s seed when one is set, so results are
+    /// reproducible, and afresh on every call otherwise.
+    pub fn suggest_by_entropy_sampled(&self, upto: usize, sample: usize) -> Vec<String> {
+        Self::rank_by_entropy_over(
+            &self.sample_answers(sample),
+            &self.answers,
+            &self.known_hits(),
+            &self.answers,
+            upto,
+        )
+    }
+
+    /// Like `suggest_by_entropy_sampled`, but probes are drawn from the
+    /// allowed words as in `suggest_any`, where sampling saves the most.
+    pub fn suggest_any_by_entropy_sampled(&self, upto: usize, sample: usize) -> Vec<String> {
+        Self::rank_by_entropy_over(
+            &self.sample_answers(sample),
+            &self.answers,
+            &self.known_hits(),
+            self.probes(),
+            upto,
+        )
+    }
+
+    // at most `sample` of the remaining words, picked at random from the seed
+    fn sample_answers(&self, sample: usize) -> BTreeSet<String> {
+        let seed = self
+            .seed
+            .unwrap_or_else(|| RandomState::new().build_hasher().finish());
+        let mut rng = SplitMix64(seed);
+        // a partial Fisher-Yates shuffle picks the sample
+        let mut words: Vec<&String> = self.answers.iter().collect();
+        let sample = sample.min(words.len());
+        for idx in 0..sample {
+            let pick = idx + rng.below(words.len() - idx);
+            words.swap(idx, pick);
+        }
+        words[..sample].iter().map(|w| (*w).clone()).collect()
+    }
+
+    /// Makes sampled scoring such as `suggest_by_entropy_sampled`
+    /// reproducible. Scoring that doesn't sample is unaffected.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+
+    /// Like `suggest_by_entropy`, but probes are drawn from the allowed words
+    /// as in `suggest_any`.
+    pub fn suggest_any_by_entropy(&self, upto: usize) -> Vec<String> {
+        self.rank_with(&Strategy::Entropy, self.probes(), upto)
+    }
+
+    /// The allowed word with the highest expected information against the
+    /// whole dictionary, i.e. the best opening guess whatever has been guessed
+    /// since. It is computed once and cached until words are added; use
+    /// `set_best_first_guess` to restore a value saved from an earlier run.
+    pub fn best_first_guess(&self) -> String {
+        self.first_guess
+            .get_or_init(|| {
+                Self::rank_by_entropy_over(
+                    &self.dictionary,
+                    &self.dictionary,
+                    &[None; N],
+                    &self.allowed,
+                    1,
+                )
+                .pop()
+                .unwrap_or_default()
+            })
+            .clone()
+    }
+
+    /// Sets the value `best_first_guess` returns, skipping the computation.
+    /// The word is sanitized as in `insert`; returns false and leaves things
+    /// unchanged unless it is then one of the allowed words.
+    pub fn set_best_first_guess(&mut self, word: String) -> bool {
+        match self.sanitize(&word) {
+            Some(word) if self.allowed.contains(&word) => {
+                self.first_guess = OnceLock::from(word);
+                self.book = OnceLock::new();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // the expected information of `probe`'s feedback against the remaining
+    // words, in bits
+    fn entropy(&self, probe: &str) -> f64 {
+        Self::entropy_over(&self.answers, &self.known_hits(), probe)
+    }
+
+    // the expected information of `probe`'s feedback against `answers`,
+    // which all have the `locked` letters in place, see `locked_buckets`
+    fn entropy_over(answers: &BTreeSet<String>, locked: &[Option<char>; N], probe: &str) -> f64 {
+        let buckets = Self::locked_buckets(answers, probe, locked);
+        Self::bucket_entropy(answers.len(), buckets.values()).sum()
+    }
+
+    // ranks `probes` by `entropy_over` against `answers`, breaking ties as
+    // `rank_iter` does with `possible` as the words that could be the answer
+    fn rank_by_entropy_over<'a, I>(
+        answers: &BTreeSet<String>,
+        possible: &BTreeSet<String>,
+        locked: &[Option<char>; N],
+        probes: I,
+        upto: usize,
+    ) -> Vec<String>
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        let scored = score_all(probes, |probe| Self::entropy_over(answers, locked, probe));
+        let mut heap: BinaryHeap<Ranked> = scored
+            .into_iter()
+            .map(|(score, word)| Ranked {
+                key: score,
+                score,
+                possible: possible.contains(word),
+                word,
+            })
+            .collect();
+        std::iter::from_fn(|| heap.pop())
+            .take(upto)
+            .map(|r| r.word.clone())
+            .collect()
+    }
+
+    // the information each feedback group contributes, in bits
+    fn bucket_entropy<'a, I>(total: usize, sizes: I) -> impl Iterator<Item = f64> + 'a
+    where
+        I: IntoIterator<Item = &'a usize> + 'a,
+    {
+        let total = total as f64;
+        sizes.into_iter().map(move |n| {
+            let p = *n as f64 / total;
+            -p * p.log2()
+        })
+    }
+
+    /// Returns `suggest(upto)` with the number of candidates each suggestion
+    /// is expected to leave, assuming every remaining word is equally likely
+    /// to be the answer: the sum of the squared feedback group sizes over the
+    /// number of remaining words.
+    pub fn suggest_with_expected_remaining(&self, upto: usize) -> Vec<(String, f64)> {
+        let total = self.answers.len() as f64;
+        self.suggest(upto)
+            .into_iter()
+            .map(|word| {
+                let squares: usize = self.remaining_buckets(&word).values().map(|n| n * n).sum();
+                (word, squares as f64 / total)
+            })
+            .collect()
+    }
+
+    /// Returns up to `upto` candidates ranked by the size of the largest group
+    /// of remaining words that would share the same feedback, smallest first.
+    /// Ties prefer candidates that could themselves be the answer.
+    pub fn suggest_minimax(&self, upto: usize) -> Vec<String> {
+        self.rank_with(&Strategy::Minimax, &self.answers, upto)
+    }
+
+    /// Like `suggest_minimax`, but probes are drawn from the allowed words as
+    /// in `suggest_any`.
+    pub fn suggest_any_minimax(&self, upto: usize) -> Vec<String> {
+        self.rank_with(&Strategy::Minimax, self.probes(), upto)
+    }
+
+    // the most remaining words that could share `probe`'s feedback
+    fn worst_bucket(&self, probe: &str) -> usize {
+        self.remaining_buckets(probe)
+            .values()
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// What guessing `guess` would reveal: for each feedback it could get, in
+    /// `M`/`C`/`H` form, how many remaining candidates would give it. The
+    /// counts add up to `remaining_count()`, and the largest is the most
+    /// candidates that could be left afterwards.
+    ///
+    /// ```
+    /// use wordl::Wordl;
+    ///
+    /// let mut w = Wordl::new();
+    /// for word in ["cants", "pants", "rants", "slump"] {
+    ///     w.insert(word.to_string());
+    /// }
+    /// let buckets = w.simulate("pants");
+    /// assert_eq!(buckets["MHHHH"], 2);
+    /// assert_eq!(buckets["HHHHH"], 1);
+    /// ```
+    pub fn simulate(&self, guess: &str) -> BTreeMap<String, usize> {
+        self.remaining_buckets(&guess.trim().to_lowercase())
+    }
+
+    // `buckets` of the remaining candidates, skipping the positions the
+    // guesses have already solved where it can
+    fn remaining_buckets(&self, probe: &str) -> BTreeMap<String, usize> {
+        Self::locked_buckets(&self.answers, probe, &self.known_hits())
+    }
+
+    // the same as `buckets` for answers that all have the `locked` letters in
+    // place, but faster late in a game. A probe with those letters in place
+    // too gets a Hit there from every answer, and the Hits use up exactly
+    // those letters, so only the free positions need comparing. Other probes
+    // take the full path.
+    fn locked_buckets(
+        answers: &BTreeSet<String>,
+        probe: &str,
+        locked: &[Option<char>; N],
+    ) -> BTreeMap<String, usize> {
+        let guess: Vec<char> = probe.trim().to_lowercase().chars().collect();
+        let fits = guess.len() == N
+            && locked
+                .iter()
+                .zip(&guess)
+                .all(|(l, c)| l.is_none_or(|l| l == *c));
+        if !fits {
+            return Self::buckets(answers, probe);
+        }
+        let free: Vec<usize> = (0..N).filter(|idx| locked[*idx].is_none()).collect();
+        let mut result = BTreeMap::new();
+        let mut answer = Vec::with_capacity(N);
+        for word in answers {
+            answer.clear();
+            answer.extend(word.chars());
+            if answer.len() != N {
+                let feedback = Self::evaluate(word, probe).feedback();
+                *result.entry(feedback).or_insert(0) += 1;
+                continue;
+            }
+            // the answer letters left over once the free positions' Hits are
+            // taken, few enough that a list beats a map
+            let mut unmatched: Vec<char> = free
+                .iter()
+                .filter(|idx| answer[**idx] != guess[**idx])
+                .map(|idx| answer[*idx])
+                .collect();
+            let mut feedback = ['H'; N];
+            for idx in &free {
+                let c = guess[*idx];
+                if answer[*idx] == c {
+                    continue;
+                }
+                feedback[*idx] = match unmatched.iter().position(|u| *u == c) {
+                    Some(pos) => {
+                        unmatched.swap_remove(pos);
+                        'C'
+                    }
+                    None => 'M',
+                };
+            }
+            *result.entry(feedback.iter().collect()).or_insert(0) += 1;
+        }
+        result
+    }
+
+    // groups the remaining words by the feedback `probe` would get if each of
+    // them were the answer, counting the words in each group
+    fn buckets(answers: &BTreeSet<String>, probe: &str) -> BTreeMap<String, usize> {
+        let mut result = BTreeMap::new();
+        // a probe of the wrong length gets no feedback to split on
+        if probe.trim().chars().count() != N {
+            return result;
+        }
+        for answer in answers {
+            let feedback = Self::evaluate(answer, probe).feedback();
+            *result.entry(feedback).or_insert(0) += 1;
+        }
+        result
+    }
+
+    /// Shows how the current `Strategy` scores `word`, as the named parts that
+    /// add up to its score. `word` need not be a candidate. A custom scorer's
+    /// score is reported as a single part.
+    pub fn explain(&self, word: &str) -> ScoreBreakdown {
+        let word = word.trim().to_lowercase();
+        if let Some(scorer) = &self.scorer {
+            let score = scorer.score(&word, self);
+            return ScoreBreakdown {
+                strategy: None,
+                score,
+                components: vec![(word, score)],
+                buckets: BTreeMap::new(),
+            };
+        }
+        let mut buckets = BTreeMap::new();
+        let components: Vec<(String, f64)> = match self.strategy {
+            Strategy::Frequency => self
+                .positional_parts(&word, &self.solved_positions())
+                .map(|(c, part)| (c.to_string(), part))
+                .collect(),
+            Strategy::Entropy => {
+                buckets = self.remaining_buckets(&word);
+                let entropy = Self::bucket_entropy(self.answers.len(), buckets.values());
+                buckets.keys().cloned().zip(entropy).collect()
+            }
+            Strategy::Minimax => {
+                buckets = self.remaining_buckets(&word);
+                vec![("worst".to_string(), self.worst_bucket(&word) as f64)]
+            }
+            Strategy::LetterCoverage => self
+                .coverage_parts(&word)
+                .into_iter()
+                .map(|(c, part)| (c.to_string(), part))
+                .collect(),
+            Strategy::Commonness => vec![(word.clone(), self.commonness_of(&word))],
+        };
+        ScoreBreakdown {
+            strategy: Some(self.strategy),
+            // the same score the strategy ranks `word` with
+            score: self.strategy.score(&word, self),
+            components,
+            buckets,
+        }
+    }
+
+    /// Records a guess and drops every candidate inconsistent with the guesses so far.
+    /// A guess that is all Hits leaves only itself, if it was a candidate.
+    pub fn guess(&mut self, word: Word<N>) {
+        log::debug!("guess {} ({})", word.letters(), word.feedback());
+        let solved = word.0.iter().all(|l| matches!(l, Letter::Hit(_)));
+        let letters = word.letters();
+        self.guesses.push(word);
+        let before = self.answers.len();
+        if solved {
+            self.answers.retain(|k| *k == letters);
+        } else {
+            let valid = self.filter(&self.guesses);
+            log::trace!("constraints {:?}", valid);
+            self.answers.retain(|k| valid.is_valid(k));
+        }
+        self.freq = Self::make_char_frequency(self.answers.iter());
+        self.last_gain = information_gain(before, self.answers.len());
+        log::debug!("candidates {} -> {}", before, self.answers.len());
+    }
+
+    /// How many bits of information the last guess revealed, `log2` of how
+    /// many times fewer candidates it left: 1 for halving them, 0 for a guess
+    /// that ruled nothing out. After `apply_guesses` this covers the whole
+    /// batch. `None` before any guess, after `undo` or `reset`, or when no
+    /// candidates were left to narrow down.
+    pub fn last_information_gain(&self) -> Option<f64> {
+        self.last_gain
+    }
+
+    /// Records several guesses at once, e.g. from a game tracked elsewhere,
+    /// with the same result as calling `guess` for each but filtering the
+    /// candidates only once.
+    pub fn apply_guesses<I: IntoIterator<Item = Word<N>>>(&mut self, words: I) {
+        let before = self.guesses.len();
+        self.guesses.extend(words);
+        if self.guesses.len() == before {
+            return;
+        }
+        let remaining = self.answers.len();
+        let valid = self.filter(&self.guesses);
+        self.answers.retain(|k| valid.is_valid(k));
+        self.freq = Self::make_char_frequency(self.answers.iter());
+        self.last_gain = information_gain(remaining, self.answers.len());
+    }
+
+    /// Parses a guess and its feedback as `Word::parse` does and records it.
+    ///
+    /// ```
+    /// use wordl::Wordl;
+    ///
+    /// let mut w = Wordl::with_default_dictionary();
+    /// w.guess_str("crane", "MMMMM")?;
+    /// w.guess_str("moist", "CMMCM")?;
+    /// assert!(w.candidates().contains(&"slump".to_string()));
+    /// assert!(w.guess_str("slump", "HHHH").is_err());
+    /// # Ok::<(), wordl::ParseError>(())
+    /// ```
+    pub fn guess_str(&mut self, guess: &str, feedback: &str) -> Result<(), ParseError> {
+        self.guess(Word::parse(guess, feedback)?);
+        Ok(())
+    }
+
+    /// Like `guess`, but rejects a guess that isn't one of the allowed words
+    /// or comes after every guess is used up, leaving the game unchanged, as
+    /// strict play would.
+    pub fn guess_checked(&mut self, word: Word<N>) -> Result<(), WordlError> {
+        if self.guesses.len() >= self.max_guesses {
+            return Err(WordlError::OutOfGuesses {
+                max_guesses: self.max_guesses,
+            });
+        }
+        let letters = word.letters();
+        if !self.allowed.contains(&letters) {
+            return Err(WordlError::UnknownWord(letters));
+        }
+        self.guess(word);
+        Ok(())
+    }
+
+    /// Takes back the last guess, returning it, and restores the candidates
+    /// it had ruled out.
+    pub fn undo(&mut self) -> Option<Word<N>> {
+        let word = self.guesses.pop()?;
+        self.refilter();
+        self.last_gain = None;
+        Some(word)
+    }
+
+    /// Starts a new game: forgets every guess and required letter and makes
+    /// the whole dictionary possible again, without reloading it.
+    pub fn reset(&mut self) {
+        self.guesses.clear();
+        self.required.clear();
+        self.last_gain = None;
+        self.refilter();
+    }
+
+    /// Records that the answer contains `c` somewhere, without a guess to
+    /// show it: unlike a `Contains` in a guess this rules out no position.
+    /// Useful when importing what is known from elsewhere.
+    pub fn require_letter(&mut self, c: char) {
+        for c in c.to_lowercase() {
+            self.required.insert(c);
+        }
+        let valid = self.filter(&self.guesses);
+        self.answers.retain(|k| valid.is_valid(k));
+        self.freq = Self::make_char_frequency(self.answers.iter());
+    }
+
+    /// Whether `word` fits every guess and required letter so far, whether or
+    /// not it is in the dictionary. Nothing is changed.
+    pub fn is_consistent(&self, word: &str) -> bool {
+        self.filter(&self.guesses)
+            .is_valid(&word.trim().to_lowercase())
+    }
+
+    // the fewest of each letter the answer has given `guesses` and the
+    // required letters
+    fn min_counts(&self, guesses: &[Word<N>]) -> BTreeMap<char, usize> {
+        let mut result = Self::make_min_counts(guesses);
+        for c in &self.required {
+            result.entry(*c).or_insert(1);
+        }
+        result
+    }
+
+    // the `Filter` for `guesses` along with the required letters
+    fn filter(&self, guesses: &[Word<N>]) -> Filter<N> {
+        Filter::new(
+            Self::make_hits(guesses),
+            self.min_counts(guesses),
+            Self::make_max_counts(guesses),
+            Self::make_excludes(guesses),
+            Self::make_excludes_at(guesses),
+        )
+    }
+
+    // the dictionary words consistent with `guesses`
+    fn valid_answers(&self, guesses: &[Word<N>]) -> BTreeSet<String> {
+        let valid = self.filter(guesses);
+        self.dictionary
+            .iter()
+            .filter(|k| valid.is_valid(k))
+            .cloned()
+            .collect()
+    }
+
+    // rebuilds the candidates from the dictionary and the current guesses
+    fn refilter(&mut self) {
+        self.answers = self.valid_answers(&self.guesses);
+        self.freq = Self::make_char_frequency(self.answers.iter());
+    }
+
+    /// Plays the game against `answer`, repeatedly guessing the top
+    /// suggestion, and returns the words guessed in order. Stops once the
+    /// answer is guessed or no candidates remain. Equal scores are ranked
+    /// alphabetically, so the sequence is reproducible.
+    pub fn auto_solve(&mut self, answer: &str) -> Vec<String> {
+        let answer = answer.to_lowercase();
+        let mut result = vec![];
+        while let Some(guess) = self.suggest(1).pop() {
+            self.guess(Self::evaluate(&answer, &guess));
+            let solved = guess == answer;
+            result.push(guess);
+            if solved {
+                break;
+            }
+        }
+        result
+    }
+
+    /// Computes the feedback Wordle would give for `guess` when the hidden
+    /// word is `answer`.
+    ///
+    /// A letter is only marked `Contains` while there are occurrences of it
+    /// in the answer left over after every `Hit` has been assigned, so
+    /// repeated letters are never over-counted. Both words are trimmed and
+    /// compared lowercase. Passing the result to `guess` never rules out
+    /// `answer` itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `guess` is not `N` characters long once trimmed.
+    pub fn evaluate(answer: &str, guess: &str) -> Word<N> {
+        let answer: Vec<char> = answer.trim().to_lowercase().chars().collect();
+        let guess: Vec<char> = guess.trim().to_lowercase().chars().collect();
+        assert_eq!(guess.len(), N, "guess must be {} characters", N);
+        // count the answer letters that are not already hits
+        let mut unmatched: BTreeMap<char, u32> = BTreeMap::new();
+        for (idx, c) in answer.iter().enumerate() {
+            if guess.get(idx) != Some(c) {
+                *unmatched.entry(*c).or_insert(0) += 1;
+            }
+        }
+        Word(std::array::from_fn(|idx| {
+            let c = guess[idx];
+            if answer.get(idx) == Some(&c) {
+                return Letter::Hit(c);
+            }
+            match unmatched.get_mut(&c) {
+                Some(n) if *n > 0 => {
+                    *n -= 1;
+                    Letter::Contains(c)
+                }
+                _ => Letter::Miss(c),
+            }
+        }))
+    }
+
+    /// Counts the letters at each of the `N` positions of `vals`, the
+    /// frequencies the solver scores candidates with. Words are taken as
+    /// given, without lowercasing or checking them; letters past the `N`th
+    /// are ignored and a shorter word only counts towards its own positions.
+    ///
+    /// ```
+    /// use wordl::Wordl;
+    ///
+    /// let freq = Wordl::<5>::make_char_frequency(["slump", "stump", "crane"]);
+    /// assert_eq!(freq[0].rate('s'), 2.0 / 3.0);
+    /// assert_eq!(freq[2].mode(), Some(('u', 2.0 / 3.0)));
+    /// assert_eq!(freq[4].rate('e'), 1.0 / 3.0);
+    /// ```
+    pub fn make_char_frequency<I>(vals: I) -> [CharFreq; N]
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut result: [CharFreq; N] = std::array::from_fn(|_| CharFreq::default());
+        for word in vals {
+            for (idx, c) in word.as_ref().chars().take(N).enumerate() {
+                result[idx].insert(c);
+            }
+        }
+        result
+    }
+
+    // every Hit or Contains in a guess is a separate occurrence of that letter
+    // in the answer, so the answer needs at least as many of each letter as
+    // any single guess revealed
+    fn make_min_counts(words: &[Word<N>]) -> BTreeMap<char, usize> {
+        let mut result: BTreeMap<char, usize> = BTreeMap::new();
+        for instance in words {
+            for (c, n) in Self::make_present_counts(instance) {
+                let min = result.entry(c).or_insert(n);
+                *min = (*min).max(n);
+            }
+        }
+        result
+    }
+
+    /// Looks for feedback that contradicts itself, which would leave no
+    /// possible answer. This usually means a guess was entered wrongly.
+    pub fn check_constraints(&self) -> Result<(), Contradiction> {
+        let mut hits: [Option<char>; N] = [None; N];
+        for instance in &self.guesses {
+            for (position, l) in instance.0.iter().enumerate() {
+                if let Letter::Hit(c) = l {
+                    match hits[position] {
+                        Some(first) if first != *c => {
+                            return Err(Contradiction::ConflictingHits {
+                                position,
+                                first,
+                                second: *c,
+                            });
+                        }
+                        _ => hits[position] = Some(*c),
+                    }
+                }
+            }
+        }
+        let filter = self.filter(&self.guesses);
+        for (position, hit) in hits.iter().enumerate() {
+            if let Some(c) = hit {
+                if filter.excludes.contains(c) {
+                    return Err(Contradiction::ExcludedHit(*c));
+                }
+                if filter.excludes_at[position].contains(c) {
+                    return Err(Contradiction::HitExcludedAt {
+                        letter: *c,
+                        position,
+                    });
+                }
+            }
+        }
+        for (c, at_least) in &filter.min_counts {
+            if let Some(at_most) = filter.max_counts.get(c) {
+                if at_most < at_least {
+                    return Err(Contradiction::CountConflict {
+                        letter: *c,
+                        at_least: *at_least,
+                        at_most: *at_most,
+                    });
+                }
+            }
+        }
+        let required: usize = filter.min_counts.values().sum();
+        if required > N {
+            return Err(Contradiction::TooManyLetters {
+                required,
+                length: N,
+            });
+        }
+        Ok(())
+    }
+
+    fn make_hits(words: &[Word<N>]) -> [Option<char>; N] {
+        let mut result = [None; N];
+        for instance in words {
+            for (idx, l) in instance.0.iter().enumerate() {
+                if let Letter::Hit(c) = l {
+                    result[idx] = Some(*c);
+                }
+            }
+        }
+        result
+    }
+
+    // a Contains rules its letter out of that position, and so does a Miss
+    // for a letter the same guess marked Hit or Contains elsewhere
+    fn make_excludes_at(words: &[Word<N>]) -> [BTreeSet<char>; N] {
+        let mut result: [BTreeSet<char>; N] = std::array::from_fn(|_| BTreeSet::new());
+        for instance in words {
+            let present = Self::make_present_counts(instance);
+            for (idx, l) in instance.0.iter().enumerate() {
+                match l {
+                    Letter::Contains(c) => {
+                        result[idx].insert(*c);
+                    }
+                    Letter::Miss(c) if present.contains_key(c) => {
+                        result[idx].insert(*c);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        result
+    }
+
+    // the letters each position has been tried with without a Hit
+    fn make_tried_at(words: &[Word<N>]) -> [BTreeSet<char>; N] {
+        let mut result: [BTreeSet<char>; N] = std::array::from_fn(|_| BTreeSet::new());
+        for instance in words {
+            for (idx, l) in instance.0.iter().enumerate() {
+                if let Letter::Miss(c) | Letter::Contains(c) = l {
+                    result[idx].insert(*c);
+                }
+            }
+        }
+        result
+    }
+
+    // counts the letters of a single guess that were marked Hit or Contains
+    fn make_present_counts(word: &Word<N>) -> BTreeMap<char, usize> {
+        let mut result = BTreeMap::new();
+        for l in word.0.iter() {
+            if let Letter::Hit(c) | Letter::Contains(c) = l {
+                *result.entry(*c).or_insert(0) += 1;
+            }
+        }
+        result
+    }
+
+    // a Miss only rules a letter out entirely when the same guess didn't also
+    // mark that letter as Hit or Contains
+    fn make_excludes(words: &[Word<N>]) -> BTreeSet<char> {
+        let mut result = BTreeSet::new();
+        for instance in words {
+            let present = Self::make_present_counts(instance);
+            for l in instance.0.iter() {
+                if let Letter::Miss(c) = l {
+                    if !present.contains_key(c) {
+                        result.insert(*c);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    // a Miss for a letter that the same guess also marked Hit or Contains caps
+    // the answer at exactly that many occurrences of the letter
+    fn make_max_counts(words: &[Word<N>]) -> BTreeMap<char, usize> {
+        let mut result: BTreeMap<char, usize> = BTreeMap::new();
+        for instance in words {
+            let present = Self::make_present_counts(instance);
+            for l in instance.0.iter() {
+                if let Letter::Miss(c) = l {
+                    if let Some(n) = present.get(c) {
+                        let max = result.entry(*c).or_insert(*n);
+                        *max = (*max).min(*n);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    // hard mode only asks that known Hits stay in place and known Contains
+    // letters are used somewhere, Misses may be reused
+    fn make_is_hard_mode_valid(words: &[Word<N>]) -> Box<dyn Fn(&str) -> bool> {
+        let min_counts = Self::make_min_counts(words);
+        let hits = Self::make_hits(words);
+        Box::new(move |s: &str| -> bool {
+            for (idx, c) in s.chars().enumerate() {
+                if let Some(Some(h)) = hits.get(idx) {
+                    if *h != c {
+                        return false;
+                    }
+                }
+            }
+            has_min_counts(s, &min_counts)
+        })
+    }
+
+    /// Builds the `Filter` that `guess` applies, from the guesses so far.
+    ///
+    /// ```
+    /// use wordl::{Word, Wordl};
+    ///
+    /// let guesses: Vec<Word> = vec![Wordl::evaluate("slump", "souls")];
+    /// let filter = Wordl::make_is_valid(&guesses);
+    /// assert_eq!(filter.hits()[0], Some('s'));
+    /// assert!(filter.is_valid("slump"));
+    /// assert!(!filter.is_valid("souls"));
+    /// ```
+    pub fn make_is_valid(words: &[Word<N>]) -> Filter<N> {
+        Filter::new(
+            Self::make_hits(words),
+            Self::make_min_counts(words),
+            Self::make_max_counts(words),
+            Self::make_excludes(words),
+            Self::make_excludes_at(words),
+        )
+    }
+}
+
+/// The constraints a set of guesses puts on the answer, see
+/// `Wordl::make_is_valid`.
+#[derive(Debug, Clone)]
+pub struct Filter<const N: usize = 5> {
+    hits: [Option<char>; N],
+    min_counts: BTreeMap<char, usize>,
+    max_counts: BTreeMap<char, usize>,
+    excludes: BTreeSet<char>,
+    excludes_at: [BTreeSet<char>; N],
+    // `excludes` and the letters of `min_counts` from a to z as bitsets of
+    // `letter_bit`, so presence is checked with a couple of bitwise ops
+    excluded_mask: u32,
+    required_mask: u32,
+    // the minimum counts the masks can't check: counts above one and letters
+    // outside a to z
+    counted: BTreeMap<char, usize>,
+}
+
+// the bit for `c` in a letter mask, for a to z only
+fn letter_bit(c: char) -> Option<u32> {
+    c.is_ascii_lowercase().then(|| 1 << (c as u32 - 'a' as u32))
+}
+
+// the mask of `letters`, leaving out any outside a to z
+fn letter_mask<'a, I: IntoIterator<Item = &'a char>>(letters: I) -> u32 {
+    letters
+        .into_iter()
+        .filter_map(|c| letter_bit(*c))
+        .fold(0, |mask, bit| mask | bit)
+}
+
+impl<const N: usize> Filter<N> {
+    fn new(
+        hits: [Option<char>; N],
+        min_counts: BTreeMap<char, usize>,
+        max_counts: BTreeMap<char, usize>,
+        excludes: BTreeSet<char>,
+        excludes_at: [BTreeSet<char>; N],
+    ) -> Self {
+        let excluded_mask = letter_mask(&excludes);
+        let required_mask = letter_mask(min_counts.keys());
+        let counted = min_counts
+            .iter()
+            .filter(|(c, n)| **n > 1 || letter_bit(**c).is_none())
+            .map(|(c, n)| (*c, *n))
+            .collect();
+        Filter {
+            hits,
+            min_counts,
+            max_counts,
+            excludes,
+            excludes_at,
+            excluded_mask,
+            required_mask,
+            counted,
+        }
+    }
+
+    /// Whether `word` could still be the answer.
+    pub fn is_valid(&self, word: &str) -> bool {
+        let mut len = 0;
+        let mut letters = 0;
+        for (idx, c) in word.chars().enumerate() {
+            if idx >= N {
+                return false;
+            }
+            if let Some(h) = self.hits[idx] {
+                if h != c {
+                    return false;
+                }
+            }
+            if self.excludes_at[idx].contains(&c) {
+                return false;
+            }
+            match letter_bit(c) {
+                Some(bit) => letters |= bit,
+                None if self.excludes.contains(&c) => return false,
+                None => {}
+            }
+            len += 1;
+        }
+        if len != N
+            || letters & self.excluded_mask != 0
+            || letters & self.required_mask != self.required_mask
+        {
+            return false;
+        }
+        if !has_min_counts(word, &self.counted) {
+            return false;
+        }
+        for (c, max) in &self.max_counts {
+            if word.chars().filter(|cc| cc == c).count() > *max {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// The letter known to be at each position.
+    pub fn hits(&self) -> &[Option<char>; N] {
+        &self.hits
+    }
+
+    /// The fewest times each letter known to be present must appear.
+    pub fn contains(&self) -> &BTreeMap<char, usize> {
+        &self.min_counts
+    }
+
+    /// The most times a letter may appear, for letters whose count is known.
+    pub fn max_counts(&self) -> &BTreeMap<char, usize> {
+        &self.max_counts
+    }
+
+    /// Letters that are not in the answer at all.
+    pub fn excludes(&self) -> &BTreeSet<char> {
+        &self.excludes
+    }
+
+    /// Letters known to be present, but not at the given position.
+    pub fn excludes_at(&self) -> &[BTreeSet<char>; N] {
+        &self.excludes_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "rayon")]
+    use crate::score_all;
+    use crate::take_highest;
+    use crate::CharFreq;
+    use crate::Contradiction;
+    use crate::Filter;
+    use crate::FrequencySource;
+    #[cfg(feature = "serde")]
+    use crate::GameState;
+    use crate::Letter;
+    use crate::ParseError;
+    use crate::Strategy;
+    use crate::Suggestion;
+    use crate::Word;
+    use crate::Wordl;
+    use crate::WordlError;
+    use crate::DEFAULT_DICTIONARY;
+    use crate::DEFAULT_FIRST_GUESS;
+    #[cfg(feature = "rayon")]
+    use crate::{ProbeFrequency, Scorer};
+    use std::collections::BTreeSet;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_valid() {
+        let words = vec![
+            Word([
+                Letter::Miss('c'),
+                Letter::Miss('c'),
+                Letter::Contains('e'),
+                Letter::Miss('c'),
+                Letter::Miss('c'),
+            ]),
+            Word([
+                Letter::Contains('e'),
+                Letter::Miss('c'),
+                Letter::Contains('g'),
+                Letter::Miss('c'),
+                Letter::Miss('c'),
+            ]),
+            Word([
+                Letter::Contains('e'),
+                Letter::Contains('g'),
+                Letter::Contains('g'),
+                Letter::Miss('c'),
+                Letter::Miss('c'),
+            ]),
+            Word([
+                Letter::Contains('e'),
+                Letter::Miss('c'),
+                Letter::Miss('e'),
+                Letter::Miss('c'),
+                Letter::Contains('y'),
+            ]),
+        ];
+        let f = Wordl::make_is_valid(&words);
+        assert!(!f.is_valid("match"));
+        // 'e' was marked Contains at index 0, so it can't sit there
+        assert!(!f.is_valid("eggyy"));
+        // the Miss('e') alongside a Contains('e') only caps 'e' at one
+        assert!(f.is_valid("geygg"));
+        assert!(!f.is_valid("geyge"));
+    }
+
+    #[test]
+    fn miss_does_not_exclude_letter_that_is_also_a_hit() {
+        // the second 'o' of "boost" is a Miss because "ghost" has only one 'o'
+        let words: Vec<Word> = vec![Wordl::evaluate("ghost", "boost")];
+        let f = Wordl::make_is_valid(&words);
+        assert!(f.is_valid("ghost"));
+        assert!(!f.is_valid("roost"));
+    }
+
+    #[test]
+    fn filter_accepts_exactly_the_words_with_the_same_feedback() {
+        let words: Vec<&str> = DEFAULT_DICTIONARY.lines().collect();
+        let answers = words.iter().step_by(400).chain(&["agley", "abbde"]);
+        for answer in answers {
+            for guess in ["eerie", "abcbb", "floor", "geese", "llama", "crane"] {
+                let feedback: Word = Wordl::evaluate(answer, guess);
+                let f = Wordl::make_is_valid(std::slice::from_ref(&feedback));
+                for candidate in words.iter().chain(&["abase", "abdeb"]) {
+                    assert_eq!(
+                        f.is_valid(candidate),
+                        Wordl::<5>::evaluate(candidate, guess) == feedback,
+                        "{} against {} with answer {}",
+                        candidate,
+                        guess,
+                        answer
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn miss_on_a_repeated_letter_caps_its_count() {
+        // the first 'o' of "floor" is a Hit and the second a Miss, so the
+        // answer has exactly one 'o'
+        let mut w = Wordl::new();
+        for word in ["flock", "flood", "floss", "float"] {
+            w.insert(word.to_string());
+        }
+        w.guess(Word::parse("floor", "HHHMM").unwrap());
+        assert_eq!(w.candidates(), vec!["float", "flock", "floss"]);
+        let filter = Wordl::make_is_valid(&w.guesses);
+        assert_eq!(filter.max_counts().get(&'o'), Some(&1));
+    }
+
+    // `Filter::is_valid` as it was before letter masks, checking each letter
+    // against the sets
+    fn reference_is_valid(f: &Filter, word: &str) -> bool {
+        word.chars().count() == 5
+            && word.chars().enumerate().all(|(idx, c)| {
+                !f.excludes().contains(&c)
+                    && f.hits()[idx].is_none_or(|h| h == c)
+                    && !f.excludes_at()[idx].contains(&c)
+            })
+            && f.contains()
+                .iter()
+                .all(|(c, min)| word.chars().filter(|cc| cc == c).count() >= *min)
+            && f.max_counts()
+                .iter()
+                .all(|(c, max)| word.chars().filter(|cc| cc == c).count() <= *max)
+    }
+
+    #[test]
+    fn letter_masks_filter_like_the_letter_sets() {
+        let mut words: Vec<&str> = DEFAULT_DICTIONARY.lines().collect();
+        words.extend([
+            "señor", "niñas", "ñandu", "eerie", "boost", "toolong", "abc",
+        ]);
+        let mut cases: Vec<Vec<Word>> = vec![
+            vec![Wordl::evaluate("ghost", "boost")],
+            vec![Word::parse("floor", "HHHMM").unwrap()],
+            vec![
+                Wordl::evaluate("eerie", "crane"),
+                Wordl::evaluate("eerie", "emcee"),
+            ],
+            vec![Word::parse("señor", "MMHCM").unwrap()],
+            vec![Word::parse("niñas", "CMMMM").unwrap()],
+            vec![Word::parse("crane", "MMMM?").unwrap()],
+        ];
+        for answer in words.iter().step_by(97) {
+            cases.push(vec![
+                Wordl::evaluate(answer, "tares"),
+                Wordl::evaluate(answer, "eerie"),
+            ]);
+        }
+        for guesses in &cases {
+            let filter = Wordl::make_is_valid(guesses);
+            for word in &words {
+                assert_eq!(
+                    filter.is_valid(word),
+                    reference_is_valid(&filter, word),
+                    "{:?} {}",
+                    guesses,
+                    word
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn the_answer_survives_its_own_feedback() {
+        let tricky = [
+            ("abbey", "babee"),
+            ("abbey", "keeps"),
+            ("eerie", "emcee"),
+            ("ghost", "boost"),
+            ("speed", "erase"),
+            ("llama", "hello"),
+            ("array", "rarer"),
+            ("cocoa", "occur"),
+            ("mamma", "ammam"),
+            ("aaaaa", "abaaa"),
+        ];
+        let words: Vec<&str> = DEFAULT_DICTIONARY.lines().collect();
+        let sampled = words.iter().step_by(89).flat_map(|answer| {
+            words
+                .iter()
+                .step_by(113)
+                .map(move |guess| (*answer, *guess))
+        });
+        for (answer, guess) in tricky.into_iter().chain(sampled) {
+            let feedback: Word = Wordl::evaluate(answer, guess);
+            let filter = Wordl::make_is_valid(std::slice::from_ref(&feedback));
+            assert!(filter.is_valid(answer), "{} {}", answer, feedback);
+
+            let mut w = Wordl::new();
+            w.insert(answer.to_string());
+            w.guess(feedback);
+            assert_eq!(w.candidates(), vec![answer]);
+        }
+    }
+
+    #[test]
+    fn min_counts_track_repeated_letters() {
+        // _ _ E _ _
+        // E _ G _ _
+        // E G G _ _
+        // Y _ _ _ _
+        // -> EGGY
+
+        let words = vec![
+            Word([
+                Letter::Miss('c'),
+                Letter::Miss('c'),
+                Letter::Contains('e'),
+                Letter::Miss('c'),
+                Letter::Miss('c'),
+            ]),
+            Word([
+                Letter::Contains('e'),
+                Letter::Miss('c'),
+                Letter::Contains('g'),
+                Letter::Miss('c'),
+                Letter::Miss('c'),
+            ]),
+            Word([
+                Letter::Contains('e'),
+                Letter::Contains('g'),
+                Letter::Contains('g'),
+                Letter::Miss('c'),
+                Letter::Miss('c'),
+            ]),
+            Word([
+                Letter::Miss('c'),
+                Letter::Miss('c'),
+                Letter::Miss('e'),
+                Letter::Miss('c'),
+                Letter::Contains('y'),
+            ]),
+        ];
+        let expected = [('e', 1), ('g', 2), ('y', 1)].into_iter().collect();
+        assert_eq!(Wordl::make_min_counts(&words), expected);
+    }
+
+    #[test]
+    fn parse_maps_feedback_onto_guess() {
+        let word: Word = Word::parse("souls", "HMCCC").unwrap();
+        let expected = [
+            Letter::Hit('s'),
+            Letter::Miss('o'),
+            Letter::Contains('u'),
+            Letter::Contains('l'),
+            Letter::Contains('s'),
+        ];
+        assert_eq!(format!("{:?}", word.0), format!("{:?}", expected));
+    }
+
+    #[test]
+    fn parse_rejects_bad_input() {
+        assert_eq!(
+            Word::<5>::parse("soul", "HMCCC").unwrap_err(),
+            ParseError::WrongLength
+        );
+        assert_eq!(
+            Word::<5>::parse("souls", "HMCC").unwrap_err(),
+            ParseError::WrongLength
+        );
+        assert_eq!(
+            Word::<5>::parse("souls", "HMXCC").unwrap_err(),
+            ParseError::InvalidFeedback('X')
+        );
+    }
+
+    #[test]
+    fn words_work_as_set_keys() {
+        let mut patterns: BTreeSet<Word> = BTreeSet::new();
+        patterns.insert(Wordl::evaluate("slump", "souls"));
+        patterns.insert(Wordl::evaluate("slump", "sumps"));
+        patterns.insert(Wordl::evaluate("slump", "souls"));
+        assert_eq!(patterns.len(), 2);
+        // same feedback on different letters is a different word
+        patterns.insert(Word::parse("sumps", "HMMMM").unwrap());
+        patterns.insert(Word::parse("souls", "HMMMM").unwrap());
+        assert_eq!(patterns.len(), 4);
+        assert!(Letter::Hit('a') < Letter::Hit('b'));
+    }
+
+    #[test]
+    fn from_pair_maps_each_feedback_character() {
+        assert_eq!(Letter::from_pair('H', 's'), Ok(Letter::Hit('s')));
+        assert_eq!(Letter::from_pair('C', 's'), Ok(Letter::Contains('s')));
+        assert_eq!(Letter::from_pair('M', 's'), Ok(Letter::Miss('s')));
+        assert_eq!(Letter::from_pair('?', 's'), Ok(Letter::Unknown('s')));
+        assert_eq!(Letter::from_pair('🟩', 's'), Ok(Letter::Hit('s')));
+        assert_eq!(Letter::from_pair('🟨', 's'), Ok(Letter::Contains('s')));
+        assert_eq!(Letter::from_pair('⬛', 's'), Ok(Letter::Miss('s')));
+        assert_eq!(Letter::from_pair('⬜', 's'), Ok(Letter::Miss('s')));
+        assert_eq!(Letter::from_pair('❔', 's'), Ok(Letter::Unknown('s')));
+        assert_eq!(
+            Letter::from_pair('h', 's'),
+            Err(ParseError::InvalidFeedback('h'))
+        );
+    }
+
+    #[test]
+    fn from_emoji_row_matches_letter_feedback() {
+        let expected: Word = Word::parse("souls", "HMCCM").unwrap();
+        assert_eq!(
+            Word::from_emoji_row("souls", "🟩⬛🟨🟨⬛").unwrap(),
+            expected
+        );
+        assert_eq!(
+            Word::from_emoji_row("souls", "🟩⬜🟨🟨⬜").unwrap(),
+            expected
+        );
+        assert_eq!(
+            Word::from_emoji_row("souls", "🟩⬛\u{fe0f}🟨🟨⬛\u{fe0f}").unwrap(),
+            expected
+        );
+        assert_eq!(
+            Word::<5>::from_emoji_row("souls", "🟩⬛🟨🟨").unwrap_err(),
+            ParseError::WrongLength
+        );
+        assert_eq!(
+            Word::<5>::from_emoji_row("souls", "🟩⬛🟦🟨⬛").unwrap_err(),
+            ParseError::InvalidFeedback('🟦')
+        );
+    }
+
+    #[test]
+    fn parse_accepts_emoji_feedback() {
+        let expected: Word = Word::parse("souls", "HMCCM").unwrap();
+        assert_eq!(Word::parse("SoUlS", "🟩⬛🟨🟨⬜").unwrap(), expected);
+        assert_eq!(
+            Word::<5>::parse("souls", "🟩⬛🟨🟨!").unwrap_err(),
+            ParseError::InvalidFeedback('!')
+        );
+        // a grid row has to be all squares
+        assert_eq!(
+            Word::<5>::from_emoji_row("souls", "🟩M🟨🟨⬛").unwrap_err(),
+            ParseError::InvalidFeedback('M')
+        );
+    }
+
+    #[test]
+    fn parse_and_evaluate_trim_their_input() {
+        let expected: Word = Word::parse("souls", "HMCCM").unwrap();
+        assert_eq!(Word::parse(" SOuls\n", "\tHMCCM ").unwrap(), expected);
+        assert_eq!(
+            Word::<5>::parse(" soul ", "HMCCM").unwrap_err(),
+            ParseError::WrongLength
+        );
+        let evaluated: Word = Wordl::evaluate(" Slump ", "  souls\n");
+        assert_eq!(evaluated, Wordl::evaluate("slump", "souls"));
+    }
+
+    #[test]
+    fn unknown_letters_impose_no_constraints() {
+        let mut w = Wordl::with_default_dictionary();
+        let total = w.remaining_count();
+        w.guess(Word::parse("souls", "?????").unwrap());
+        assert_eq!(w.remaining_count(), total);
+        assert_eq!(w.check_constraints(), Ok(()));
+
+        // only the known Hit at the start filters
+        let partial: Word = Word::parse("souls", "H????").unwrap();
+        assert_eq!(partial.to_string(), "S ? ? ? ?");
+        assert_eq!(partial.emoji_row(), "🟩❔❔❔❔");
+        w.guess(partial);
+        let starts_with_s = DEFAULT_DICTIONARY
+            .lines()
+            .filter(|w| w.starts_with('s'))
+            .count();
+        assert_eq!(w.remaining_count(), starts_with_s);
+    }
+
+    #[test]
+    fn evaluate_marks_hits_contains_and_misses() {
+        let word: Word = Wordl::evaluate("souls", "solum");
+        let expected: Word = Word::parse("solum", "HHCCM").unwrap();
+        assert_eq!(format!("{:?}", word), format!("{:?}", expected));
+    }
+
+    #[test]
+    fn evaluate_does_not_over_count_duplicates() {
+        let word: Word = Wordl::evaluate("allot", "lolly");
+        let expected: Word = Word::parse("lolly", "CCHMM").unwrap();
+        assert_eq!(format!("{:?}", word), format!("{:?}", expected));
+    }
+
+    #[test]
+    fn suggest_returns_highest_scoring_words_first() {
+        let mut w = Wordl::new();
+        for word in ["abcde", "abcee", "xbcde", "qrstu"] {
+            w.insert(word.to_string());
+        }
+        // abcde shares the most common letter at every position
+        assert_eq!(w.suggest(1), vec!["abcde"]);
+        assert_eq!(w.suggest(4)[3], "qrstu");
+    }
+
+    #[test]
+    fn six_letter_guess_narrows_candidates() {
+        let mut w = Wordl::<6>::default();
+        for word in ["planet", "plants", "pardon", "mirror"] {
+            assert!(w.insert(word.to_string()));
+        }
+        assert!(!w.insert("plane".to_string()));
+        assert_eq!(w.len_of_word(), 6);
+        assert_eq!(w.suggest(10).len(), 4);
+        w.guess(Wordl::evaluate("planet", "plants"));
+        assert_eq!(w.suggest(10), vec!["planet"]);
+    }
+
+    #[test]
+    fn minimax_ranks_by_largest_bucket() {
+        let mut w = Wordl::new();
+        for word in ["abcde", "abcdf", "abcdg", "xyzwv"] {
+            w.insert(word.to_string());
+        }
+        // "xyzwv" can't tell the three "abcd" words apart
+        assert_eq!(
+            w.suggest_minimax(4),
+            vec!["abcde", "abcdf", "abcdg", "xyzwv"]
+        );
+    }
+
+    #[test]
+    fn detailed_minimax_matches_suggest_from_allowed() {
+        let mut w = Wordl::new();
+        for word in ["cants", "pants"] {
+            w.insert(word.to_string());
+        }
+        w.insert_allowed("apple".to_string());
+        w.set_strategy(Strategy::Minimax);
+        // every word tells the two apart, so the ones that could win go first
+        let words = |detailed: Vec<Suggestion>| -> Vec<String> {
+            detailed.into_iter().map(|s| s.word).collect()
+        };
+        assert_eq!(w.suggest_from_allowed(3), vec!["cants", "pants", "apple"]);
+        assert_eq!(words(w.suggest_any_detailed(3)), w.suggest_from_allowed(3));
+        assert_eq!(words(w.suggest_detailed(3)), w.suggest(3));
+    }
+
+    #[test]
+    fn entropy_ranks_by_expected_information() {
+        let mut w = Wordl::new();
+        for word in ["abcde", "abcdf", "abcdg", "xyzwv"] {
+            w.insert(word.to_string());
+        }
+        assert_eq!(w.suggest_by_entropy(1), vec!["abcde"]);
+        assert_eq!(w.suggest_by_entropy(4)[3], "xyzwv");
+    }
+
+    #[test]
+    fn display_shows_feedback_per_letter() {
+        assert_eq!(Letter::Hit('s').to_string(), "S");
+        assert_eq!(Letter::Contains('l').to_string(), "l");
+        assert_eq!(Letter::Miss('o').to_string(), "_");
+        let word: Word = Word::parse("solum", "HMCCC").unwrap();
+        assert_eq!(word.to_string(), "S _ l u m");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn game_state_round_trips_through_json() {
+        let words = ["souls", "solum", "slump", "mould", "sumps"];
+        let mut w = Wordl::new();
+        for word in words {
+            w.insert(word.to_string());
+        }
+        w.guess(Wordl::evaluate("slump", "souls"));
+        let json = serde_json::to_string(&w.state()).unwrap();
+        assert!(json.starts_with(r#"{"guesses":[[{"hit":"s"},"#));
+
+        let state: GameState = serde_json::from_str(&json).unwrap();
+        assert_eq!(state.remaining, w.state().remaining);
+        let mut restored = Wordl::new();
+        for word in words {
+            restored.insert(word.to_string());
+        }
+        restored.restore(state);
+        assert_eq!(restored.suggest(5), w.suggest(5));
+    }
+
+    #[test]
+    fn default_dictionary_is_five_letter_words() {
+        let w = Wordl::with_default_dictionary();
+        let words = w.suggest(usize::MAX);
+        assert!(!words.is_empty());
+        assert!(words.iter().all(|word| word.chars().count() == 5));
+    }
+
+    #[test]
+    fn dictionary_and_guesses_ignore_case() {
+        let mut w = Wordl::new();
+        w.insert("HELLO".to_string());
+        w.insert("World".to_string());
+        w.guess(Word::parse("hello", "HHHHH").unwrap());
+        assert_eq!(w.suggest(5), vec!["hello"]);
+
+        let mut w = Wordl::new();
+        w.insert("ÉCLAT".to_string());
+        assert_eq!(w.suggest(1), vec!["éclat"]);
+
+        let word: Word = Word::parse("HeLLo", "HHHHH").unwrap();
+        assert_eq!(word.to_string(), "H E L L O");
+        let word: Word = Wordl::evaluate("HELLO", "hello");
+        assert_eq!(word.feedback(), "HHHHH");
+    }
+
+    #[test]
+    fn insert_rejects_non_conforming_entries() {
+        let mut w = Wordl::new();
+        for word in [
+            "", "   ", "cat", "hello  ", " world", "letters", "it's", "ab1de",
+        ] {
+            w.insert(word.to_string());
+        }
+        assert_eq!(w.suggest(10), vec!["hello", "world"]);
+    }
+
+    #[test]
+    fn remaining_count_tracks_guesses() {
+        let mut w = Wordl::new();
+        for word in ["souls", "solum", "slump", "mould", "sumps"] {
+            w.insert(word.to_string());
+        }
+        assert_eq!(w.remaining_count(), 5);
+        w.guess(Word::parse("souls", "HMHCM").unwrap());
+        assert_eq!(w.suggest_with_count(1), (vec!["slump".to_string()], 1));
+    }
+
+    #[test]
+    fn candidates_match_the_validity_predicate() {
+        let words = ["souls", "solum", "slump", "mould", "sumps", "still"];
+        let mut w = Wordl::new();
+        for word in words {
+            w.insert(word.to_string());
+        }
+        let guesses: Vec<Word> = vec![Wordl::evaluate("slump", "mould")];
+        let valid = Wordl::make_is_valid(&guesses);
+        for guess in guesses {
+            w.guess(guess);
+        }
+        let mut expected: Vec<&str> = words
+            .into_iter()
+            .filter(|word| valid.is_valid(word))
+            .collect();
+        expected.sort();
+        assert!(!expected.is_empty());
+        assert_eq!(w.candidates(), expected);
+    }
+
+    #[test]
+    fn suggest_any_proposes_ruled_out_probes() {
+        let mut w = Wordl::new();
+        for word in ["slump", "plums", "sumps", "mould", "crane"] {
+            w.insert(word.to_string());
+        }
+        w.guess(Wordl::evaluate("slump", "sumps"));
+        assert_eq!(w.candidates(), vec!["slump"]);
+        assert_eq!(w.suggest(5), vec!["slump"]);
+        // ruled out words may still be useful probes
+        assert_eq!(w.suggest_any(5).len(), 5);
+
+        // hard mode requires the Hit 's' up front plus 'u', 'm' and 'p'
+        w.set_hard_mode(true);
+        assert_eq!(w.suggest_any(5), vec!["slump", "sumps"]);
+    }
+
+    #[test]
+    fn auto_solve_finds_the_answer() {
+        let mut w = Wordl::with_default_dictionary();
+        let guesses = w.auto_solve("slump");
+        assert_eq!(guesses.last().map(String::as_str), Some("slump"));
+        assert_eq!(w.candidates(), vec!["slump"]);
+
+        let mut again = Wordl::with_default_dictionary();
+        assert_eq!(again.auto_solve("slump"), guesses);
+    }
+
+    #[test]
+    fn cached_frequency_follows_the_dictionary() {
+        let mut w = Wordl::new();
+        for word in ["souls", "solum", "slump", "mould", "sumps", "souls"] {
+            w.insert(word.to_string());
+        }
+        assert_eq!(w.freq, Wordl::make_char_frequency(w.answers.iter()));
+        let before = w.suggest(5);
+        w.guess(Word::parse("mould", "MMCCM").unwrap());
+        assert_eq!(w.freq, Wordl::make_char_frequency(w.answers.iter()));
+        assert_ne!(w.suggest(5), before);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_scoring_matches_sequential() {
+        let mut w = Wordl::with_default_dictionary();
+        let score = |s: &String| s.chars().filter(|c| "aeiou".contains(*c)).count();
+        let sequential: Vec<(usize, &String)> = w.answers.iter().map(|p| (score(p), p)).collect();
+        assert_eq!(score_all(&w.answers, score), sequential);
+
+        // the suggestions match a one word at a time ranking with the same
+        // tie-breaks
+        fn rank_sequentially(w: &Wordl, scorer: &dyn Scorer, probes: Vec<&String>) -> Vec<String> {
+            let sign = if scorer.higher_is_better() { 1.0 } else { -1.0 };
+            let mut scored: Vec<(f64, bool, &String)> = probes
+                .into_iter()
+                .map(|p| (sign * scorer.score(p, w), w.answers.contains(p), p))
+                .collect();
+            scored.sort_by(|a, b| {
+                b.0.partial_cmp(&a.0)
+                    .unwrap()
+                    .then(b.1.cmp(&a.1))
+                    .then(a.2.cmp(b.2))
+            });
+            scored
+                .into_iter()
+                .take(5)
+                .map(|(_, _, p)| p.clone())
+                .collect()
+        }
+        w.guess(Wordl::evaluate("slump", "crane"));
+        w.guess(Wordl::evaluate("slump", "moist"));
+        for strategy in [
+            Strategy::Frequency,
+            Strategy::Entropy,
+            Strategy::Minimax,
+            Strategy::LetterCoverage,
+        ] {
+            w.set_strategy(strategy);
+            let answers = w.answers.iter().collect();
+            assert_eq!(w.suggest(5), rank_sequentially(&w, &strategy, answers));
+        }
+        assert_eq!(
+            w.suggest_any(5),
+            rank_sequentially(&w, &ProbeFrequency, w.probes())
+        );
+        w.set_strategy(Strategy::Entropy);
+        assert_eq!(
+            w.suggest_any_by_entropy(5),
+            rank_sequentially(&w, &Strategy::Entropy, w.probes())
+        );
+    }
+
+    #[test]
+    fn from_path_reports_missing_files() {
+        let result = Wordl::<5>::from_path("./does-not-exist.txt");
+        assert!(matches!(result, Err(WordlError::Io(_))));
+    }
+
+    #[test]
+    fn from_path_loads_words() {
+        let w = Wordl::<5>::from_path("./words.txt").unwrap();
+        assert_eq!(w.remaining_count(), 5757);
+    }
+
+    #[test]
+    fn allowed_probes_are_scored_against_answers() {
+        let answers = ["abcde", "abcdf", "abcdg", "abcdh"].map(String::from);
+        let w: Wordl = Wordl::from_lists(answers, ["fghxy".to_string()]);
+        assert_eq!(w.remaining_count(), 4);
+        // only "fghxy" can tell every answer apart, but it is never the answer
+        assert_eq!(w.suggest_any_minimax(1), vec!["fghxy"]);
+        assert_eq!(w.suggest_any_by_entropy(1), vec!["fghxy"]);
+        assert_eq!(w.suggest_minimax(1), vec!["abcde"]);
+        assert!(!w.suggest(5).contains(&"fghxy".to_string()));
+    }
+
+    #[test]
+    fn candidates_pages_cover_the_ranking() {
+        let mut w = Wordl::with_default_dictionary();
+        w.guess(Wordl::evaluate("slump", "crane"));
+        let all = w.suggest(usize::MAX);
+        let mut pages = Vec::new();
+        for offset in (0..all.len() + 7).step_by(7) {
+            let (page, total) = w.candidates_page(offset, 7);
+            assert_eq!(total, all.len());
+            assert!(page.len() <= 7);
+            pages.extend(page);
+        }
+        assert_eq!(pages, all);
+        assert!(w.candidates_page(all.len(), 7).0.is_empty());
+    }
+
+    #[test]
+    fn is_solved_once_the_answer_is_known() {
+        let mut w = Wordl::new();
+        for word in ["souls", "solum", "slump", "mould", "sumps"] {
+            w.insert(word.to_string());
+        }
+        assert!(!w.is_solved());
+        assert_eq!(w.remaining().count(), 5);
+
+        w.guess(Wordl::evaluate("slump", "souls"));
+        assert!(w.is_solved());
+        assert_eq!(w.remaining().collect::<Vec<_>>(), vec!["slump"]);
+
+        let mut w = Wordl::new();
+        w.guess(Word::parse("slump", "HHHHH").unwrap());
+        assert!(w.is_solved());
+    }
+
+    #[test]
+    fn solution_is_the_last_candidate() {
+        let mut w = Wordl::new();
+        for word in ["souls", "solum", "slump", "mould", "sumps"] {
+            w.insert(word.to_string());
+        }
+        w.guess(Wordl::evaluate("slump", "souls"));
+        assert_eq!(w.solution(), Some("slump".to_string()));
+    }
+
+    #[test]
+    fn no_solution_while_candidates_remain() {
+        let mut w = Wordl::with_default_dictionary();
+        assert_eq!(w.solution(), None);
+        w.guess(Wordl::evaluate("slump", "crane"));
+        assert!(w.remaining_count() > 1);
+        assert_eq!(w.solution(), None);
+    }
+
+    #[test]
+    fn guesses_remaining_counts_down() {
+        let mut w = Wordl::with_default_dictionary();
+        assert_eq!(w.guesses_remaining(), 6);
+        w.guess(Wordl::evaluate("slump", "crane"));
+        w.guess(Wordl::evaluate("slump", "moist"));
+        assert_eq!(w.guesses_remaining(), 4);
+        w.set_max_guesses(1);
+        assert_eq!(w.guesses_remaining(), 0);
+    }
+
+    #[test]
+    fn games_are_lost_once_the_guesses_run_out() {
+        let mut w = Wordl::with_default_dictionary();
+        w.set_max_guesses(2);
+        w.guess_checked(Wordl::evaluate("slump", "crane")).unwrap();
+        assert!(!w.is_solved() && !w.is_lost());
+        assert_eq!(w.guesses_remaining(), 1);
+
+        let mut won = w.clone();
+        won.guess_checked(Wordl::evaluate("slump", "slump"))
+            .unwrap();
+        assert!(won.is_solved() && !won.is_lost());
+
+        w.guess_checked(Wordl::evaluate("slump", "tired")).unwrap();
+        assert!(!w.is_solved() && w.is_lost());
+        let result = w.guess_checked(Wordl::evaluate("slump", "slump"));
+        assert!(matches!(
+            result,
+            Err(WordlError::OutOfGuesses { max_guesses: 2 })
+        ));
+        assert_eq!(w.guessed_words(), vec!["crane", "tired"]);
+    }
+
+    #[test]
+    fn all_hits_leave_only_the_answer() {
+        let mut w = Wordl::with_default_dictionary();
+        w.guess(Word::parse("slump", "HHHHH").unwrap());
+        assert_eq!(w.candidates(), vec!["slump"]);
+        assert!(w.is_solved());
+        assert_eq!(w.solution(), Some("slump".to_string()));
+        w.undo();
+        assert_eq!(w.remaining_count(), w.original_size());
+    }
+
+    #[test]
+    fn is_consistent_checks_a_word_without_guessing() {
+        let mut w = Wordl::with_default_dictionary();
+        w.guess(Wordl::evaluate("slump", "crane"));
+        w.guess(Wordl::evaluate("slump", "moist"));
+        let before = w.candidates();
+        assert!(w.is_consistent("slump"));
+        assert!(w.is_consistent(" SLUMP"));
+        assert!(!w.is_consistent("moist"));
+        assert!(!w.is_consistent("plumb"));
+        assert_eq!(w.candidates(), before);
+    }
+
+    #[test]
+    fn apply_guesses_matches_guessing_one_at_a_time() {
+        let guesses = [
+            Wordl::evaluate("slump", "crane"),
+            Wordl::evaluate("slump", "moist"),
+            Wordl::evaluate("slump", "plumb"),
+        ];
+        let mut one_by_one = Wordl::with_default_dictionary();
+        for word in guesses.clone() {
+            one_by_one.guess(word);
+        }
+        let mut batch = Wordl::with_default_dictionary();
+        batch.apply_guesses(guesses);
+        assert_eq!(batch.candidates(), one_by_one.candidates());
+        assert_eq!(batch.guessed_words(), one_by_one.guessed_words());
+        assert_eq!(batch.suggest(3), one_by_one.suggest(3));
+    }
+
+    #[test]
+    fn last_information_gain_follows_the_candidate_count() {
+        let mut w = Wordl::with_default_dictionary();
+        assert_eq!(w.last_information_gain(), None);
+        let total = w.remaining_count();
+        w.guess(Wordl::evaluate("slump", "tares"));
+        let remaining = w.remaining_count();
+        let gain = w.last_information_gain().unwrap();
+        assert!(gain > 3.0);
+        assert!((2f64.powf(gain) - total as f64 / remaining as f64).abs() < 1e-9);
+
+        // a guess fitting every remaining candidate tells nothing
+        w.guess(Wordl::evaluate("slump", "tares"));
+        assert_eq!(w.last_information_gain(), Some(0.0));
+        w.undo();
+        assert_eq!(w.last_information_gain(), None);
+    }
+
+    #[test]
+    fn iterating_a_solver_visits_the_candidates() {
+        let mut w = Wordl::with_default_dictionary();
+        w.guess(Wordl::evaluate("slump", "crane"));
+        let mut count = 0;
+        for word in &w {
+            assert!(w.is_consistent(word));
+            count += 1;
+        }
+        assert_eq!(count, w.remaining_count());
+        assert!((&w).into_iter().eq(w.remaining()));
+    }
+
+    #[test]
+    fn undo_restores_the_previous_candidates() {
+        let mut w = Wordl::with_default_dictionary();
+        let total = w.remaining_count();
+        w.guess(Wordl::evaluate("slump", "crane"));
+        let before = w.candidates();
+        w.guess(Wordl::evaluate("slump", "moist"));
+        assert_ne!(w.candidates(), before);
+
+        assert!(w.undo().is_some());
+        assert_eq!(w.candidates(), before);
+        assert!(w.undo().is_some());
+        assert_eq!(w.remaining_count(), total);
+        assert!(w.undo().is_none());
+    }
+
+    #[test]
+    fn repeated_contains_require_that_many_letters() {
+        let mut w = Wordl::new();
+        for word in ["basis", "bison", "oasis", "dairy"] {
+            w.insert(word.to_string());
+        }
+        let guess = Wordl::evaluate("basis", "sense");
+        assert_eq!(guess.to_string(), "s _ _ s _");
+        w.guess(guess);
+        assert_eq!(w.candidates(), vec!["basis", "oasis"]);
+    }
+
+    #[test]
+    fn best_first_guess_maximizes_entropy_over_the_dictionary() {
+        let mut w = Wordl::new();
+        for word in ["plate", "slate", "grate", "crate"] {
+            w.insert(word.to_string());
+        }
+        for word in ["clasp", "spilt"] {
+            w.insert_allowed(word.to_string());
+        }
+        // "clasp" tells all four answers apart, every other word leaves two
+        // sharing the same feedback
+        assert_eq!(w.best_first_guess(), "clasp");
+        w.guess(Wordl::evaluate("plate", "slate"));
+        assert_eq!(w.best_first_guess(), "clasp");
+
+        assert!(w.set_best_first_guess(" Spilt".to_string()));
+        assert_eq!(w.best_first_guess(), "spilt");
+        assert!(!w.set_best_first_guess("abc".to_string()));
+        assert!(!w.set_best_first_guess("zzzzz".to_string()));
+        assert_eq!(w.best_first_guess(), "spilt");
+        assert_eq!(w.opening_book().first(), "spilt");
+        w.insert_allowed("corgi".to_string());
+        assert_eq!(w.best_first_guess(), "clasp");
+    }
+
+    // slow in debug builds, run with `cargo test --release -- --ignored`
+    #[test]
+    #[ignore]
+    fn default_first_guess_is_current() {
+        let mut w = Wordl::new();
+        for word in DEFAULT_DICTIONARY.lines() {
+            w.insert(word.to_string());
+        }
+        assert_eq!(w.best_first_guess(), DEFAULT_FIRST_GUESS);
+    }
+
+    #[test]
+    fn load_from_reads_words_from_any_reader() {
+        let input = Cursor::new("Souls\nsolum\n\nnope\n  slump \n".as_bytes());
+        let w = Wordl::<5>::load_from(input).unwrap();
+        assert_eq!(w.candidates(), vec!["slump", "solum", "souls"]);
+
+        let empty = Cursor::new("nope\n".as_bytes());
+        assert!(matches!(
+            Wordl::<5>::load_from(empty),
+            Err(WordlError::EmptyDictionary)
+        ));
+    }
+
+    #[test]
+    fn from_words_inserts_each_word() {
+        let w: Wordl = Wordl::from_words(vec!["apple".into(), " Grape".into(), "fig".into()]);
+        assert_eq!(w.dictionary().collect::<Vec<_>>(), vec!["apple", "grape"]);
+    }
+
+    #[test]
+    fn add_word_sanitizes_and_explains_rejections() {
+        let mut w = Wordl::new();
+        w.add_word(" Slump").unwrap();
+        w.add_word("slump").unwrap();
+        assert_eq!(w.candidates(), vec!["slump"]);
+        assert!(matches!(
+            w.add_word("slumps"),
+            Err(WordlError::BadWordLength { expected: 5, .. })
+        ));
+        assert!(
+            matches!(w.add_word("sl-mp"), Err(WordlError::BadLetters(word)) if word == "sl-mp")
+        );
+        assert_eq!(w.remaining_count(), 1);
+    }
+
+    #[test]
+    fn clear_dictionary_removes_every_word() {
+        let mut w = Wordl::with_default_dictionary();
+        w.clear_dictionary();
+        assert_eq!(w.remaining_count(), 0);
+        assert_eq!(w.original_size(), 0);
+        assert!(w.suggest_any(1).is_empty());
+        w.add_word("crane").unwrap();
+        assert_eq!(w.suggest(1), vec!["crane"]);
+        assert_eq!(w.best_first_guess(), "crane");
+    }
+
+    #[test]
+    fn clones_play_independently() {
+        let mut w = Wordl::with_default_dictionary();
+        w.guess(Wordl::evaluate("slump", "crane"));
+        let mut copy = w.clone();
+        assert_eq!(copy.suggest(5), w.suggest(5));
+
+        copy.guess(Wordl::evaluate("slump", "moist"));
+        assert_ne!(copy.remaining_count(), w.remaining_count());
+        w.guess(Wordl::evaluate("slump", "moist"));
+        assert_eq!(copy.suggest(5), w.suggest(5));
+        assert_eq!(copy.state().guesses, w.state().guesses);
+    }
+
+    #[test]
+    fn explain_adds_up_to_the_ranking_score() {
+        let mut w = Wordl::with_default_dictionary();
+        w.guess(Wordl::evaluate("slump", "crane"));
+        for strategy in [
+            Strategy::Frequency,
+            Strategy::Entropy,
+            Strategy::Minimax,
+            Strategy::LetterCoverage,
+        ] {
+            w.set_strategy(strategy);
+            let scores: Vec<f64> = w
+                .suggest(5)
+                .iter()
+                .map(|word| {
+                    let breakdown = w.explain(word);
+                    let sum: f64 = breakdown.components.iter().map(|(_, v)| v).sum();
+                    assert_eq!(breakdown.score, sum);
+                    breakdown.score
+                })
+                .collect();
+            // suggestions come out in score order
+            let mut sorted = scores.clone();
+            sorted.sort_by(|a, b| b.partial_cmp(a).unwrap());
+            if strategy == Strategy::Minimax {
+                sorted.reverse();
+            }
+            assert_eq!(scores, sorted, "{:?}", strategy);
+        }
+
+        w.set_strategy(Strategy::Frequency);
+        let breakdown = w.explain("slump");
+        assert_eq!(breakdown.score, w.positional_score("slump", &[false; 5]));
+        assert!(breakdown.buckets.is_empty());
+        w.set_strategy(Strategy::Entropy);
+        let breakdown = w.explain("slump");
+        assert_eq!(
+            breakdown.buckets.values().sum::<usize>(),
+            w.remaining_count()
+        );
+    }
+
+    #[test]
+    fn alphabet_filters_words() {
+        let words = ["niños", "ninos", "años", "señor", "sabor", "Árbol"];
+        let mut w = Wordl::new();
+        w.set_alphabet("abcdefghijklmnñopqrstuvwxyz".chars());
+        for word in words {
+            w.insert(word.to_string());
+        }
+        assert_eq!(w.candidates(), vec!["ninos", "niños", "sabor", "señor"]);
+
+        // narrowing the alphabet drops words already loaded
+        w.set_alphabet('a'..='z');
+        assert_eq!(w.candidates(), vec!["ninos", "sabor"]);
+        assert!(!w.insert("señor".to_string()));
+
+        let w = Wordl::<5>::builder()
+            .answers(["señor", "sabor", "Árbol", "ninos"])
+            .alphabet("ÁRBOLSAEÑ".chars())
+            .build()
+            .unwrap();
+        assert_eq!(w.candidates(), vec!["sabor", "señor", "árbol"]);
+    }
+
+    #[test]
+    fn game_log_json_has_an_entry_per_guess() {
+        let mut w = Wordl::with_default_dictionary();
+        assert_eq!(w.game_log_json(), "[]");
+        w.guess(Wordl::evaluate("slump", "crane"));
+        let after_crane = w.remaining_count();
+        w.guess(Wordl::evaluate("slump", "moist"));
+
+        let log: serde_json::Value = serde_json::from_str(&w.game_log_json()).unwrap();
+        let expected = serde_json::json!([
+            {"guess": "crane", "feedback": "MMMMM", "remaining_after": after_crane},
+            {"guess": "moist", "feedback": "CMMCM", "remaining_after": w.remaining_count()},
+        ]);
+        assert_eq!(log, expected);
+    }
+
+    #[test]
+    fn share_grid_renders_each_guess() {
+        let mut w = Wordl::with_default_dictionary();
+        w.guess(Wordl::evaluate("slump", "crane"));
+        w.guess(Wordl::evaluate("slump", "moist"));
+        assert_eq!(w.share_grid(), "Wordl X/6\n\n⬛⬛⬛⬛⬛\n🟨⬛⬛🟨⬛");
+
+        w.guess(Wordl::evaluate("slump", "slump"));
+        assert_eq!(
+            w.share_grid(),
+            "Wordl 3/6\n\n⬛⬛⬛⬛⬛\n🟨⬛⬛🟨⬛\n🟩🟩🟩🟩🟩"
+        );
+        for word in &w.state().guesses {
+            let emoji: Word = Word::from_emoji_row(&word.letters(), &word.emoji_row()).unwrap();
+            assert_eq!(&emoji, word);
+        }
+    }
+
+    #[test]
+    fn share_grid_counts_out_of_the_guess_limit() {
+        let mut w = Wordl::with_default_dictionary();
+        w.set_max_guesses(8);
+        w.guess(Wordl::evaluate("slump", "crane"));
+        assert_eq!(w.share_grid(), "Wordl X/8\n\n⬛⬛⬛⬛⬛");
+        w.guess(Wordl::evaluate("slump", "slump"));
+        assert_eq!(w.share_grid(), "Wordl 2/8\n\n⬛⬛⬛⬛⬛\n🟩🟩🟩🟩🟩");
+    }
+
+    #[test]
+    fn position_probabilities_follow_the_candidates() {
+        let mut w = Wordl::new();
+        for word in ["souls", "solum", "slump", "mould", "sumps"] {
+            w.insert(word.to_string());
+        }
+        let probabilities = w.position_letter_probabilities();
+        assert_eq!(probabilities[0][&'s'], 0.8);
+        assert_eq!(probabilities[0][&'m'], 0.2);
+        for position in &probabilities {
+            let total: f64 = position.values().sum();
+            assert!((total - 1.0).abs() < 1e-9);
+        }
+
+        w.guess(Word::parse("souls", "HMHCM").unwrap());
+        let probabilities = w.position_letter_probabilities();
+        assert_eq!(probabilities[1].len(), 1);
+        assert_eq!(probabilities[1][&'l'], 1.0);
+    }
+
+    #[test]
+    fn expected_remaining_averages_group_sizes() {
+        let mut w = Wordl::new();
+        for word in ["cants", "pants", "rants", "wants"] {
+            w.insert(word.to_string());
+        }
+        // every word is told apart from itself and leaves the other three
+        // together: (1² + 3²) / 4
+        let suggestions = w.suggest_with_expected_remaining(4);
+        assert_eq!(suggestions.len(), 4);
+        assert!(suggestions.iter().all(|(_, expected)| *expected == 2.5));
+
+        w.guess(Wordl::evaluate("cants", "tacks"));
+        assert_eq!(
+            w.suggest_with_expected_remaining(1),
+            vec![("cants".to_string(), 1.0)]
+        );
+    }
+
+    #[test]
+    fn check_constraints_reports_contradictions() {
+        let check = |rows: &[(&str, &str)]| {
+            let mut w = Wordl::new();
+            for (guess, feedback) in rows {
+                w.guess(Word::parse(guess, feedback).unwrap());
+            }
+            w.check_constraints()
+        };
+        assert_eq!(check(&[("souls", "HMCCM"), ("slump", "HHCMM")]), Ok(()));
+        assert_eq!(
+            check(&[("souls", "HMMMM"), ("mould", "HMMMM")]),
+            Err(Contradiction::ConflictingHits {
+                position: 0,
+                first: 's',
+                second: 'm',
+            })
+        );
+        assert_eq!(
+            check(&[("souls", "HMMMM"), ("pasta", "MMMMM")]),
+            Err(Contradiction::ExcludedHit('s'))
+        );
+        assert_eq!(
+            check(&[("souls", "HMMMM"), ("stare", "CMMMM")]),
+            Err(Contradiction::HitExcludedAt {
+                letter: 's',
+                position: 0,
+            })
+        );
+        assert_eq!(
+            check(&[("abcde", "CCCMM"), ("fghij", "CCCMM")]),
+            Err(Contradiction::TooManyLetters {
+                required: 6,
+                length: 5,
+            })
+        );
+        assert_eq!(
+            check(&[("melee", "MCMCM"), ("sheep", "MMCMM")]),
+            Err(Contradiction::CountConflict {
+                letter: 'e',
+                at_least: 2,
+                at_most: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn guess_checked_rejects_unknown_words() {
+        let mut w = Wordl::with_default_dictionary();
+        let total = w.remaining_count();
+        let result = w.guess_checked(Word::parse("xqzvk", "MMMMM").unwrap());
+        assert!(matches!(result, Err(WordlError::UnknownWord(word)) if word == "xqzvk"));
+        assert_eq!(w.remaining_count(), total);
+        assert!(w.state().guesses.is_empty());
+
+        w.guess_checked(Wordl::evaluate("slump", "crane")).unwrap();
+        assert!(w.remaining_count() < total);
+    }
+
+    #[test]
+    fn suggest_from_allowed_may_propose_non_answers() {
+        let mut w: Wordl = Wordl::from_lists(
+            ["cants", "pants", "rants", "wants"].map(String::from),
+            ["caper", "bract"].map(String::from),
+        );
+        for strategy in [Strategy::Entropy, Strategy::Minimax] {
+            w.set_strategy(strategy);
+            // no answer tells the others apart, "caper" tells apart all four
+            assert!(w.candidates().contains(&w.suggest(1)[0]));
+            assert_eq!(w.suggest_from_allowed(1), vec!["caper"], "{:?}", strategy);
+        }
+        w.set_strategy(Strategy::LetterCoverage);
+        assert_eq!(w.suggest(6).len(), 4);
+        assert_eq!(w.suggest_from_allowed(6).len(), 6);
+    }
+
+    #[test]
+    fn char_freq_mode_is_the_most_common_letter() {
+        let mut freq = CharFreq::default();
+        assert_eq!(freq.mode(), None);
+        for c in "sassy".chars() {
+            freq.insert(c);
+        }
+        assert_eq!(freq.mode(), Some(('s', 0.6)));
+        // ties go to the alphabetically first letter
+        freq.insert('a');
+        freq.insert('a');
+        assert_eq!(freq.mode(), Some(('a', 3.0 / 7.0)));
+
+        let mut w = Wordl::new();
+        for word in ["souls", "solum", "slump", "mould"] {
+            w.insert(word.to_string());
+        }
+        assert_eq!(w.most_likely_letters()[0], Some(('s', 0.75)));
+        w.guess(Wordl::evaluate("slump", "mould"));
+        assert_eq!(w.most_likely_letters()[1], Some(('l', 1.0)));
+        w.guess(Word::parse("slump", "MMMMM").unwrap());
+        assert_eq!(w.most_likely_letters(), [None; 5]);
+    }
+
+    #[test]
+    fn suggest_opener_uses_only_common_letters() {
+        let mut w = Wordl::with_default_dictionary();
+        let mut freq = CharFreq::default();
+        for c in DEFAULT_DICTIONARY.lines().flat_map(str::chars) {
+            freq.insert(c);
+        }
+        let openers = w.suggest_opener(5);
+        assert_eq!(openers[0], "arose");
+        for opener in &openers {
+            assert!(opener.chars().all(|c| freq.rate(c) > 0.05), "{}", opener);
+        }
+
+        // guesses don't change the opener
+        w.guess(Wordl::evaluate("slump", "crane"));
+        assert_eq!(w.suggest_opener(5), openers);
+    }
+
+    #[test]
+    fn guessed_words_lists_guesses_in_order() {
+        let mut w = Wordl::with_default_dictionary();
+        assert!(w.guessed_words().is_empty());
+        w.guess(Wordl::evaluate("slump", "crane"));
+        w.guess_str("Moist", "CMMCM").unwrap();
+        assert_eq!(w.guessed_words(), vec!["crane", "moist"]);
+        w.undo();
+        assert_eq!(w.guessed_words(), vec!["crane"]);
+    }
+
+    #[test]
+    fn candidates_always_match_filtering_the_full_dictionary() {
+        let mut w = Wordl::with_default_dictionary();
+        let guesses = ["crane", "moist", "slump"].map(|g| Wordl::evaluate("slump", g));
+        for n in 1..=guesses.len() {
+            w.guess(guesses[n - 1].clone());
+            let valid = Wordl::make_is_valid(&guesses[..n]);
+            let mut expected: Vec<&str> = DEFAULT_DICTIONARY
+                .lines()
+                .filter(|word| valid.is_valid(word))
+                .collect();
+            expected.sort();
+            assert_eq!(w.candidates(), expected);
+            assert_eq!(w.dictionary().count(), DEFAULT_DICTIONARY.lines().count());
+        }
+
+        // words added mid-game only become candidates if they fit
+        assert!(w.insert("slumz".to_string()));
+        assert_eq!(w.candidates(), vec!["slump"]);
+        w.undo();
+        assert!(w.remaining().any(|word| word == "slumz"));
+    }
+
+    #[test]
+    fn reset_restores_the_full_dictionary() {
+        let mut w = Wordl::with_default_dictionary();
+        let total = w.remaining_count();
+        w.guess(Wordl::evaluate("slump", "crane"));
+        w.guess(Wordl::evaluate("slump", "moist"));
+        assert!(w.remaining_count() < total);
+
+        w.reset();
+        assert_eq!(w.remaining_count(), total);
+        assert!(w.undo().is_none());
+    }
+
+    #[test]
+    fn letter_coverage_ignores_repeated_letters() {
+        let mut w = Wordl::new();
+        for word in ["sassy", "stare", "tears", "rates"] {
+            w.insert(word.to_string());
+        }
+        // 's' is the most common letter but "sassy" only gets credit once
+        assert_eq!(w.suggest_by_letter_coverage(4)[3], "sassy");
+    }
+
+    #[test]
+    fn weighted_alpha_changes_the_ranking() {
+        let mut w = Wordl::new();
+        for word in ["alone", "angry", "order", "world"] {
+            w.insert(word.to_string());
+        }
+        assert_eq!(w.suggest_weighted(1, 1.0), w.suggest(1));
+        assert_eq!(w.suggest_weighted(1, 1.0), vec!["alone"]);
+        assert_eq!(w.suggest_weighted(1, 0.0), vec!["order"]);
+    }
+
+    #[test]
+    fn duplicate_weight_penalizes_repeated_letters() {
+        let mut w = Wordl::new();
+        for word in ["array", "crane", "fired", "hurry", "march", "union"] {
+            w.insert(word.to_string());
+        }
+        let rank = |w: &Wordl, word: &str| w.suggest(6).iter().position(|s| s == word);
+        assert!(rank(&w, "array") < rank(&w, "crane"));
+        w.set_duplicate_weight(0.0);
+        assert!(rank(&w, "crane") < rank(&w, "array"));
+    }
+
+    #[test]
+    fn placement_bonus_prefers_known_letters_in_new_positions() {
+        let mut w = Wordl::new();
+        for word in ["mites", "smite", "times"] {
+            w.insert(word.to_string());
+        }
+        for word in ["emits", "items"] {
+            w.insert_allowed(word.to_string());
+        }
+        w.guess(Word::parse("ebony", "CMMMM").unwrap());
+        // every word has the same letters, so only the placement of 'e' can
+        // tell them apart
+        assert_eq!(w.suggest_any_weighted(2, 0.0), vec!["emits", "items"]);
+        w.set_placement_bonus(1.0);
+        assert_eq!(w.suggest_any_weighted(5, 0.0)[0], "items");
+        assert_eq!(w.suggest_any_weighted(5, 0.0)[4], "emits");
+    }
+
+    #[test]
+    fn words_of_the_wrong_length_are_ignored_when_scoring() {
+        let mut w = Wordl::new();
+        for word in ["crane", "slump", "letters"] {
+            w.insert(word.to_string());
+        }
+        // `insert` turns it away, so slip it past sanitizing
+        w.answers.insert("letters".to_string());
+        w.allowed.insert("letters".to_string());
+        for strategy in [
+            Strategy::Frequency,
+            Strategy::Entropy,
+            Strategy::Minimax,
+            Strategy::LetterCoverage,
+            Strategy::Commonness,
+        ] {
+            w.set_strategy(strategy);
+            assert_eq!(w.suggest(5), vec!["crane", "slump"], "{:?}", strategy);
+            w.explain("letters");
+        }
+        w.guess(Wordl::evaluate("slump", "crane"));
+        assert_eq!(w.candidates(), vec!["slump"]);
+    }
+
+    #[test]
+    fn known_letters_reflect_the_guesses() {
+        let mut w = Wordl::new();
+        w.guess(Wordl::evaluate("eerie", "crane"));
+        w.guess(Wordl::evaluate("eerie", "emcee"));
+        assert_eq!(w.known_hits(), [Some('e'), None, None, None, Some('e')]);
+        // three 'e's are known, two of them placed
+        assert_eq!(w.known_contains(), vec!['e', 'r']);
+        assert_eq!(
+            w.excluded_letters(),
+            "acmn".chars().collect::<BTreeSet<_>>()
+        );
+    }
+
+    #[test]
+    fn contains_letter_positions_lists_the_yellow_squares() {
+        let mut w = Wordl::new();
+        w.guess(Word::parse("sheep", "MMCMM").unwrap());
+        assert_eq!(w.contains_letter_positions('e'), vec![2]);
+        w.guess(Word::parse("eerie", "CMMMM").unwrap());
+        assert_eq!(w.contains_letter_positions('E'), vec![0, 2]);
+        assert!(w.contains_letter_positions('s').is_empty());
+    }
+
+    #[test]
+    fn unknown_letters_leave_out_what_the_guesses_revealed() {
+        let mut w = Wordl::<5>::default();
+        w.set_alphabet('a'..='z');
+        w.guess(Wordl::evaluate("slump", "crane"));
+        w.guess(Wordl::evaluate("slump", "moist"));
+        let expected: BTreeSet<char> = ('a'..='z').filter(|c| !"cranemoist".contains(*c)).collect();
+        assert_eq!(w.unknown_letters(), expected);
+
+        // without an alphabet only letters of the allowed words are listed
+        let mut w = Wordl::new();
+        w.insert("slump".to_string());
+        w.insert("crane".to_string());
+        w.guess(Word::parse("crane", "MMMM?").unwrap());
+        assert_eq!(w.unknown_letters(), "eslump".chars().collect());
+    }
+
+    #[test]
+    fn suggest_iter_ranks_like_suggest() {
+        let mut w = Wordl::with_default_dictionary();
+        w.guess(Wordl::evaluate("slump", "crane"));
+        for strategy in [Strategy::Frequency, Strategy::Minimax] {
+            w.set_strategy(strategy);
+            let (words, scores): (Vec<String>, Vec<f64>) = w.suggest_iter().take(10).unzip();
+            assert_eq!(words, w.suggest(10), "{:?}", strategy);
+            for (word, score) in words.iter().zip(scores) {
+                assert_eq!(w.explain(word).score, score);
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_samples_the_same_words() {
+        let mut w = Wordl::with_default_dictionary();
+        w.guess(Wordl::evaluate("slump", "crane"));
+        w.set_seed(7);
+        let mut other = Wordl::with_default_dictionary();
+        other.guess(Wordl::evaluate("slump", "crane"));
+        other.set_seed(7);
+        let suggestions = w.suggest_by_entropy_sampled(5, 20);
+        assert_eq!(suggestions.len(), 5);
+        assert_eq!(other.suggest_by_entropy_sampled(5, 20), suggestions);
+        assert_eq!(w.suggest_by_entropy_sampled(5, 20), suggestions);
+
+        // sampling every word is the exact computation
+        let all = w.remaining_count();
+        assert_eq!(
+            w.suggest_by_entropy_sampled(5, all),
+            w.suggest_by_entropy(5)
+        );
+    }
+
+    #[test]
+    fn sampling_every_answer_matches_the_exact_probe_ranking() {
+        let mut w = Wordl::with_default_dictionary();
+        w.guess(Wordl::evaluate("slump", "crane"));
+        w.guess(Wordl::evaluate("slump", "moist"));
+        let all = w.remaining_count();
+        assert_eq!(
+            w.suggest_any_by_entropy_sampled(5, all),
+            w.suggest_any_by_entropy(5)
+        );
+        assert_eq!(
+            w.suggest_any_by_entropy_sampled(5, all + 10),
+            w.suggest_any_by_entropy(5)
+        );
+    }
+
+    #[test]
+    fn locked_buckets_match_the_full_comparison() {
+        let base = Wordl::with_default_dictionary();
+        let probes: Vec<String> = base.dictionary().step_by(7).map(String::from).collect();
+        for answer in ["slump", "eerie", "boost", "cants"] {
+            let mut w = base.clone();
+            for guess in ["tares", "could", "pails"] {
+                w.guess(Wordl::evaluate(answer, guess));
+                let locked = w.known_hits();
+                // probes with the solved letters in place take the fast path
+                let fitting = w.candidates().into_iter().chain(probes.iter().cloned());
+                for probe in fitting {
+                    assert_eq!(
+                        Wordl::locked_buckets(&w.answers, &probe, &locked),
+                        Wordl::<5>::buckets(&w.answers, &probe),
+                        "{} {}",
+                        answer,
+                        probe
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn simulate_partitions_the_candidates() {
+        let mut w = Wordl::new();
+        for word in ["cants", "pants", "rants", "wants", "bowel", "dowel"] {
+            w.insert(word.to_string());
+        }
+        let buckets = w.simulate("caper");
+        assert_eq!(buckets.values().sum::<usize>(), w.remaining_count());
+        assert_eq!(buckets["HHMMM"], 1);
+        assert_eq!(buckets["MMMHM"], 2);
+
+        w.guess(Wordl::evaluate("pants", "bowel"));
+        let buckets = w.simulate("CAPER");
+        assert_eq!(buckets.values().sum::<usize>(), 3);
+        assert!(w.simulate("toolong").is_empty());
+    }
+
+    #[test]
+    fn required_letters_filter_by_presence_only() {
+        let mut w = Wordl::new();
+        for word in ["eclat", "cleat", "crops", "taupe"] {
+            w.insert(word.to_string());
+        }
+        w.require_letter('E');
+        assert_eq!(w.candidates(), vec!["cleat", "eclat", "taupe"]);
+        assert_eq!(w.known_contains(), vec!['e']);
+        assert!(!w.unknown_letters().contains(&'e'));
+        assert!(w.unknown_letters().contains(&'a'));
+        // a Contains in a guess would also have ruled out "eclat"
+        let mut guessed = Wordl::new();
+        for word in ["eclat", "cleat", "crops", "taupe"] {
+            guessed.insert(word.to_string());
+        }
+        guessed.guess(Word::parse("exxxx", "CMMMM").unwrap());
+        assert_eq!(guessed.candidates(), vec!["cleat", "taupe"]);
+
+        w.guess(Wordl::evaluate("cleat", "taupe"));
+        w.undo();
+        assert_eq!(w.candidates(), vec!["cleat", "eclat", "taupe"]);
+        w.reset();
+        assert_eq!(w.remaining_count(), 4);
+    }
+
+    #[test]
+    fn reduction_ratio_compares_remaining_to_original() {
+        let mut w = Wordl::with_default_dictionary();
+        assert_eq!(w.reduction_ratio(), 0.0);
+        w.guess(Wordl::evaluate("slump", "crane"));
+        let (remaining, total) = (w.remaining_count(), w.original_size());
+        assert!(remaining < total);
+        assert_eq!(total, DEFAULT_DICTIONARY.lines().count());
+        let ratio = w.reduction_ratio();
+        assert!(ratio > 0.0 && ratio < 1.0);
+        assert_eq!(ratio, 1.0 - remaining as f64 / total as f64);
+        assert_eq!(Wordl::new().reduction_ratio(), 0.0);
+    }
+
+    #[test]
+    fn answers_matching_filters_by_prefix() {
+        let mut w = Wordl::new();
+        for word in ["slump", "slate", "sumps", "souls", "crane"] {
+            w.insert(word.to_string());
+        }
+        let matching = w.answers_matching("Sl");
+        assert_eq!(matching.len(), 2);
+        assert!(matching.iter().all(|s| s.starts_with("sl")));
+        let ranked = w.suggest(5);
+        let expected: Vec<String> = ranked
+            .iter()
+            .filter(|s| s.starts_with("sl"))
+            .cloned()
+            .collect();
+        assert_eq!(matching, expected);
+        assert_eq!(w.answers_matching(""), ranked);
+        assert!(w.answers_matching("x").is_empty());
+    }
+
+    #[test]
+    fn detailed_suggestions_carry_scores() {
+        let mut w = Wordl::with_default_dictionary();
+        w.guess(Wordl::evaluate("slump", "crane"));
+        let detailed = w.suggest_detailed(5);
+        let words: Vec<String> = detailed.iter().map(|s| s.word.clone()).collect();
+        assert_eq!(words, w.suggest(5));
+        assert!(detailed
+            .windows(2)
+            .all(|pair| pair[0].score >= pair[1].score));
+        assert!(detailed.iter().all(|s| s.still_possible));
+
+        let any = w.suggest_any_detailed(w.original_size());
+        assert!(any.windows(2).all(|pair| pair[0].score >= pair[1].score));
+        let still_possible =
+            |word: &str| any.iter().find(|s| s.word == word).unwrap().still_possible;
+        assert!(still_possible("slump"));
+        assert!(!still_possible("crane"));
+    }
+
+    #[test]
+    fn frequency_source_changes_the_ranking() {
+        let mut w = Wordl::new();
+        for word in ["bills", "hills", "mills", "tolls"] {
+            w.insert(word.to_string());
+        }
+        for word in ["tacky", "tasty", "today", "token"] {
+            w.insert_allowed(word.to_string());
+        }
+        // the candidates' first letters tie, but most allowed words start
+        // with 't'
+        assert_eq!(w.suggest(1), vec!["bills"]);
+        w.set_frequency_source(FrequencySource::Allowed);
+        assert_eq!(w.suggest(1), vec!["tolls"]);
+        w.insert_allowed("bikes".to_string());
+        w.insert_allowed("bimbo".to_string());
+        w.insert_allowed("binge".to_string());
+        w.insert_allowed("bingo".to_string());
+        w.insert_allowed("biped".to_string());
+        assert_eq!(w.suggest(1), vec!["bills"]);
+    }
+
+    #[test]
+    fn ties_break_alphabetically() {
+        let (first, second) = ("hello".to_string(), "world".to_string());
+        let scored = vec![(1.0, &second), (1.0, &first)];
+        assert_eq!(take_highest(scored, 2), vec!["hello", "world"]);
+
+        let mut w = Wordl::new();
+        w.insert("world".to_string());
+        w.insert("hello".to_string());
+        assert_eq!(w.suggest(2), vec!["hello", "world"]);
+    }
+
+    #[test]
+    fn solved_positions_do_not_count_towards_scores() {
+        let mut w = Wordl::new();
+        for word in ["sandy", "scorn", "surly", "swabs"] {
+            w.insert(word.to_string());
+        }
+        // every candidate starts with 's', inflating its global frequency
+        assert_eq!(w.suggest_weighted(1, 0.0), vec!["swabs"]);
+        w.guess(Word::parse("szzzz", "HMMMM").unwrap());
+        assert_eq!(w.remaining_count(), 4);
+        assert_eq!(w.suggest_weighted(1, 0.0), vec!["sandy"]);
+    }
+
+    #[test]
+    fn probes_are_scored_at_the_solved_positions_too() {
+        let mut w = Wordl::new();
+        for word in ["slump", "stump", "sweep"] {
+            w.insert(word.to_string());
+        }
+        w.insert_allowed("tlump".to_string());
+        w.guess(Wordl::evaluate("slump", "shard"));
+        // among the candidates the solved 's' is left out, so the two tie
+        assert_eq!(w.explain("slump").score, w.explain("tlump").score);
+        // as a probe "slump" also earns the 's' every candidate starts with
+        let any = w.suggest_any_detailed(4);
+        let score = |word: &str| any.iter().find(|s| s.word == word).unwrap().score;
+        assert!((score("slump") - score("tlump") - 1.0).abs() < 1e-9);
+        assert_eq!(w.suggest_any(4), w.suggest_from_allowed(4));
+        assert_eq!(w.suggest_any_weighted(4, 1.0), w.suggest_any(4));
+    }
+
+    #[test]
+    fn suggest_adaptive_switches_to_likely_answers() {
+        let mut w = Wordl::new();
+        for word in ["cants", "pants", "rants", "wants", "bants"] {
+            w.insert(word.to_string());
+        }
+        w.insert_allowed("crwth".to_string());
+        w.insert_commonness("wants", 100.0);
+        w.insert_commonness("pants", 50.0);
+        // five candidates: probe with the word that tells most of them apart
+        assert_eq!(w.suggest_adaptive(1), w.suggest_any_by_entropy(1));
+        assert_eq!(w.suggest_adaptive(1), vec!["crwth"]);
+
+        w.set_adaptive_threshold(5);
+        assert_eq!(w.suggest_adaptive(2), vec!["wants", "pants"]);
+
+        w.set_adaptive_threshold(3);
+        w.guess(Wordl::evaluate("pants", "crwth"));
+        assert!(w.remaining_count() <= 3);
+        assert_eq!(w.suggest_adaptive(1), vec!["pants"]);
+    }
+
+    #[test]
+    fn commonness_ranks_likely_answers_first() {
+        let mut w = Wordl::new();
+        w.insert("hello".to_string());
+        w.insert("world".to_string());
+        w.insert("zzzzz".to_string());
+        // equal positional scores fall back to alphabetical order
+        assert_eq!(w.suggest(2), vec!["hello", "world"]);
+        let tsv = "world\t120\nhello\t80\nbroken line\n";
+        w.load_commonness(tsv.as_bytes()).unwrap();
+        assert_eq!(w.suggest_by_commonness(3), vec!["world", "hello", "zzzzz"]);
+    }
+}
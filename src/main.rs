@@ -1,342 +1,194 @@
-use std::cmp::Ordering;
-use std::collections::{BTreeMap, BTreeSet};
-use std::fmt::Debug;
+use std::io::{self, BufRead, Write};
+use std::process;
 
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use wordl::{Letter, Word, Wordl, WordlError};
 
-// The output is wrapped in a Result to allow matching on errors
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+struct Args {
+    dict: Option<String>,
+    count: usize,
+    interactive: bool,
 }
 
-// https://www.powerlanguage.co.uk/wordle/
-// https://github.com/charlesreid1/five-letter-words/blob/master/sgb-words.txt
-fn main() {
-    let mut w = Wordl::default();
-    if let Ok(lines) = read_lines("./words.txt") {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            if let Ok(word) = line {
-                w.dictionary.insert(word);
+// Parses the arguments after the program name.
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Args, String> {
+    let mut result = Args {
+        dict: None,
+        count: 3,
+        interactive: false,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dict" => {
+                result.dict = Some(args.next().ok_or("--dict requires a path")?);
+            }
+            "--count" => {
+                let count = args.next().ok_or("--count requires a number")?;
+                result.count = count
+                    .parse()
+                    .map_err(|_| format!("invalid --count {:?}", count))?;
             }
+            "--interactive" => result.interactive = true,
+            _ => return Err(format!("unknown argument {:?}", arg)),
         }
     }
-    let words = vec![
-        [
-            Letter::Miss('e'),
-            Letter::Miss('t'),
-            Letter::Miss('h'),
-            Letter::Miss('y'),
-            Letter::Contains('l'),
-        ],
-        [
-            Letter::Contains('l'),
-            Letter::Contains('u'),
-            Letter::Miss('b'),
-            Letter::Miss('r'),
-            Letter::Miss('a'),
-        ],
-        [
-            Letter::Hit('s'),
-            Letter::Miss('o'),
-            Letter::Contains('l'),
-            Letter::Contains('u'),
-            Letter::Contains('m'),
-        ],
-        // [
-        //     Letter::Miss('d'),
-        //     Letter::Hit('i'),
-        //     Letter::Hit('c'),
-        //     Letter::Miss('k'),
-        //     Letter::Miss('s'),
-        // ],
-        // [
-        //     Letter::Miss('l'),
-        //     Letter::Hit('i'),
-        //     Letter::Hit('c'),
-        //     Letter::Miss('i'),
-        //     Letter::Miss('t'),
-        // ],
-    ];
-    for word in words {
-        for s in w.suggest(3) {
-            println!("suggestion: {}", s);
-        }
-        println!("guessing {:?}", word);
-        w.guess(word);
+    // both modes read their guesses from stdin
+    if result.dict.as_deref() == Some("-") {
+        return Err("guesses are read from stdin, so --dict can't be -".to_string());
     }
-    for s in w.suggest(3) {
-        println!("suggestion: {}", s);
-    }
-}
-
-#[derive(Debug)]
-enum Letter {
-    Hit(char),
-    Miss(char),
-    Contains(char),
+    Ok(result)
 }
 
-type Word = [Letter; 5];
-
-#[derive(Default, Debug)]
-struct CharFreq {
-    counts: BTreeMap<char, u32>,
-    total: u32,
+// https://www.powerlanguage.co.uk/wordle/
+// https://github.com/charlesreid1/five-letter-words/blob/master/sgb-words.txt
+fn main() {
+    let args = parse_args(std::env::args().skip(1)).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        eprintln!("usage: wordl [--dict <path>] [--count <n>] [--interactive]");
+        process::exit(2);
+    });
+    let w = match &args.dict {
+        Some(path) => load_dictionary(path, io::stdin().lock()).unwrap_or_else(|e| {
+            eprintln!("unable to load dictionary {}: {}", path, e);
+            process::exit(1);
+        }),
+        None => Wordl::with_default_dictionary(),
+    };
+    if args.interactive {
+        interactive(w, args.count);
+        return;
+    }
+    play(w, args.count);
 }
 
-impl CharFreq {
-    fn insert(&mut self, c: char) {
-        self.counts.entry(c).and_modify(|c| *c += 1).or_insert(1);
-        self.total += 1;
+// Loads the dictionary at `path`, or from `stdin` when `path` is "-".
+fn load_dictionary<R: BufRead>(path: &str, stdin: R) -> Result<Wordl, WordlError> {
+    match path {
+        "-" => Wordl::load_from(stdin),
+        _ => Wordl::from_path(path),
     }
+}
 
-    fn rate(&self, c: char) -> f64 {
-        match self.total {
-            0 => 0.0,
-            _ => *self.counts.get(&c).unwrap_or(&0) as f64 / self.total as f64,
+// Reads `guess feedback` pairs from stdin, one per line, printing
+// suggestions after each and the remaining candidates at the end.
+fn play(mut w: Wordl, count: usize) {
+    for (idx, line) in io::stdin().lock().lines().enumerate() {
+        let Ok(line) = line else {
+            break;
+        };
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let word = match fields.as_slice() {
+            [] => continue,
+            [guess, feedback] => Word::parse(guess, feedback),
+            _ => {
+                eprintln!("line {}: expected a guess and its feedback", idx + 1);
+                continue;
+            }
+        };
+        match word {
+            Ok(word) => w.guess(word),
+            Err(e) => {
+                eprintln!("line {}: {}", idx + 1, e);
+                continue;
+            }
+        }
+        for s in w.suggest(count) {
+            println!("suggestion: {}", s);
         }
     }
+    println!("candidates: {}", w.candidates().join(" "));
 }
 
-struct Wordl {
-    dictionary: BTreeSet<String>,
-    guesses: Vec<Word>,
-}
-
-impl Debug for Wordl {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Wordl")
-            .field("guesses", &self.guesses)
-            .finish()
+// Prints the prompt and reads a trimmed line from stdin, None once stdin is
+// exhausted.
+fn prompt(prompt: &str) -> Option<String> {
+    print!("{}", prompt);
+    io::stdout().flush().ok()?;
+    let mut line = String::new();
+    match io::stdin().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.trim().to_string()),
     }
 }
 
-impl Wordl {
-    fn suggest(&self, upto: usize) -> Vec<String> {
-        let mut v: Vec<String> = self.dictionary.iter().cloned().collect();
-        let freq = Wordl::make_char_frequency(self.dictionary.iter());
-        let score = move |s: &String| {
-            s.chars()
-                .enumerate()
-                .fold(0.0, |acc, (idx, c)| acc + freq[idx].rate(c))
-        };
-        v.sort_by(|a, b| score(a).partial_cmp(&score(b)).unwrap_or(Ordering::Equal));
-        v.into_iter().take(upto).collect()
-    }
-
-    fn guess(&mut self, word: Word) {
-        self.guesses.push(word);
-        let guesses = &self.guesses;
-        let valid = Wordl::make_is_valid(guesses);
-        self.dictionary.retain(|k| valid(k));
-    }
-
-    fn make_char_frequency<'a, I>(vals: I) -> [CharFreq; 5]
-    where
-        I: IntoIterator<Item = &'a String>,
-    {
-        let mut result: [CharFreq; 5] = Default::default();
-        for word in vals {
-            for (idx, c) in word.chars().enumerate() {
-                result[idx].insert(c);
-            }
-        }
-        result
+// Prints the suggestions before each guess.
+#[cfg(not(feature = "tui"))]
+fn show(w: &Wordl, count: usize) {
+    for s in w.suggest(count) {
+        println!("suggestion: {}", s);
     }
+}
 
-    fn make_contains(words: &Vec<Word>) -> Vec<char> {
-        let instances: Vec<Vec<char>> = words
-            .iter()
-            .map(|w| {
-                w.iter()
-                    .filter_map(|l| match l {
-                        &Letter::Contains(c) => Some(c),
-                        &Letter::Hit(c) => Some(c),
-                        _ => None,
-                    })
-                    .collect()
-            })
-            .collect();
-        let result: Vec<char> = instances.iter().fold(vec![], |mut acc, instance| {
-            let mut stack = acc.to_vec();
-            for c in instance {
-                if let Some(pos) = stack.iter().position(|s| *s == *c) {
-                    stack.remove(pos);
-                } else {
-                    acc.push(*c);
-                }
-            }
-            acc
-        });
-        result
-    }
+// Clears the terminal and redraws the colored grid with the suggestions.
+#[cfg(feature = "tui")]
+fn show(w: &Wordl, count: usize) {
+    print!("\x1b[2J\x1b[H{}", wordl::tui::render(w, count));
+}
 
-    fn make_hits(words: &Vec<Word>) -> [Option<char>; 5] {
-        let mut result = [None; 5];
-        for instance in words {
-            for (idx, l) in instance.iter().enumerate() {
-                if let Letter::Hit(c) = l {
-                    result[idx] = Some(*c);
-                }
+fn interactive(mut w: Wordl, count: usize) {
+    loop {
+        show(&w, count);
+        // keep asking until the guess and feedback parse
+        let word = loop {
+            let Some(guess) = prompt(&format!("guess ({} letters): ", w.len_of_word())) else {
+                return;
+            };
+            let Some(feedback) = prompt("feedback (M/C/H or 🟩🟨⬛): ") else {
+                return;
+            };
+            match Word::parse(&guess, &feedback) {
+                Ok(word) => break word,
+                Err(e) => println!("{}", e),
             }
+        };
+        let solved = word.0.iter().all(|l| matches!(l, Letter::Hit(_)));
+        w.guess(word);
+        if solved {
+            println!("solved!");
+            return;
         }
-        result
-    }
-
-    fn make_excludes_at(words: &Vec<Word>) -> [BTreeSet<char>; 5] {
-        let mut result: [BTreeSet<char>; 5] = Default::default();
-        for instance in words {
-            for (idx, l) in instance.iter().enumerate() {
-                if let Letter::Contains(c) = l {
-                    result[idx].insert(*c);
+        match w.remaining_count() {
+            0 => {
+                match w.check_constraints() {
+                    Ok(()) => println!("no candidates left"),
+                    Err(e) => println!("no candidates left: {}", e),
                 }
+                return;
             }
+            n => println!("{} possibilities left", n),
         }
-        result
-    }
-
-    fn make_is_valid(words: &Vec<Word>) -> Box<dyn Fn(&str) -> bool> {
-        // expect these characters to be present somewhere in the string exactly once
-        let contains = Wordl::make_contains(words);
-        // hits are where known expected values are
-        let hits = Wordl::make_hits(words);
-        // expect none of these characters to be present in the string
-        let excludes: BTreeSet<char> = words
-            .iter()
-            .flat_map(|word| word.iter())
-            .filter_map(|l| match l {
-                Letter::Miss(c) => Some(*c),
-                _ => None,
-            })
-            .collect();
-        let excludes_at = Wordl::make_excludes_at(words);
-
-        Box::new(move |s: &str| -> bool {
-            let mut contains = contains.to_vec();
-            for (idx, c) in s.chars().enumerate() {
-                // must execute first to muate the contains vector for each char in s
-                if let Some(pos) = contains.iter().position(|cc| c == *cc) {
-                    contains.remove(pos);
-                }
-                // the subsequent predicates may be re-ordered for efficiency
-                if excludes.contains(&c) {
-                    return false;
-                }
-                if let Some(h) = hits[idx] {
-                    if h != c {
-                        return false;
-                    }
-                }
-                if excludes_at[idx].contains(&c) {
-                    return false;
-                }
-            }
-            if contains.len() > 0 {
-                return false;
-            }
-
-            true
-        })
-    }
-}
-
-impl Default for Wordl {
-    fn default() -> Self {
-        Wordl {
-            guesses: Vec::default(),
-            dictionary: BTreeSet::default(),
+        if w.is_lost() {
+            println!("out of guesses");
+            return;
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Letter;
-    use crate::Wordl;
+    use std::io::Cursor;
 
-    #[test]
-    fn test_valid() {
-        let words = vec![
-            [
-                Letter::Miss('c'),
-                Letter::Miss('c'),
-                Letter::Contains('e'),
-                Letter::Miss('c'),
-                Letter::Miss('c'),
-            ],
-            [
-                Letter::Contains('e'),
-                Letter::Miss('c'),
-                Letter::Contains('g'),
-                Letter::Miss('c'),
-                Letter::Miss('c'),
-            ],
-            [
-                Letter::Contains('e'),
-                Letter::Contains('g'),
-                Letter::Contains('g'),
-                Letter::Miss('c'),
-                Letter::Miss('c'),
-            ],
-            [
-                Letter::Miss('c'),
-                Letter::Miss('c'),
-                Letter::Miss('e'),
-                Letter::Miss('c'),
-                Letter::Contains('y'),
-            ],
-        ];
-        let f = Wordl::make_is_valid(&words);
-        assert_eq!(f(&"match"), false);
-        assert_eq!(f(&"eggyy"), true);
+    use crate::{load_dictionary, parse_args};
+
+    fn parse(args: &[&str]) -> Result<crate::Args, String> {
+        parse_args(args.iter().map(|s| s.to_string()))
     }
 
     #[test]
-    fn contains_creates_expected_vector() {
-        // _ _ E _ _
-        // E _ G _ _
-        // E G G _ _
-        // Y _ _ _ _
-        // -> EGGY
+    fn dash_dict_is_refused_in_every_mode() {
+        for args in [&["--dict", "-"][..], &["--dict", "-", "--interactive"]] {
+            let e = parse(args).err().unwrap();
+            assert_eq!(e, "guesses are read from stdin, so --dict can't be -");
+        }
+        let args = parse(&["--dict", "words.txt", "--count", "5"]).unwrap();
+        assert_eq!(args.dict.as_deref(), Some("words.txt"));
+        assert_eq!(args.count, 5);
+        assert!(!args.interactive);
+    }
 
-        let words = vec![
-            [
-                Letter::Miss('c'),
-                Letter::Miss('c'),
-                Letter::Contains('e'),
-                Letter::Miss('c'),
-                Letter::Miss('c'),
-            ],
-            [
-                Letter::Contains('e'),
-                Letter::Miss('c'),
-                Letter::Contains('g'),
-                Letter::Miss('c'),
-                Letter::Miss('c'),
-            ],
-            [
-                Letter::Contains('e'),
-                Letter::Contains('g'),
-                Letter::Contains('g'),
-                Letter::Miss('c'),
-                Letter::Miss('c'),
-            ],
-            [
-                Letter::Miss('c'),
-                Letter::Miss('c'),
-                Letter::Miss('e'),
-                Letter::Miss('c'),
-                Letter::Contains('y'),
-            ],
-        ];
-        assert_eq!(Wordl::make_contains(&words), vec!['e', 'g', 'g', 'y']);
+    #[test]
+    fn dash_loads_the_dictionary_from_stdin() {
+        let stdin = Cursor::new("souls\nslump\n".as_bytes());
+        let w = load_dictionary("-", stdin).unwrap();
+        assert_eq!(w.candidates(), vec!["slump", "souls"]);
     }
 }
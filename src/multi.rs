@@ -0,0 +1,130 @@
+use std::collections::BTreeSet;
+
+use crate::{score_all, take_highest, Word, Wordl, WordlError};
+
+/// Several boards solved together with shared guesses, as in Dordle or
+/// Quordle. Each board is an independent `Wordl`; guesses are chosen for the
+/// boards still unsolved as a whole.
+#[derive(Debug, Clone)]
+pub struct MultiWordl<const N: usize = 5> {
+    boards: Vec<Wordl<N>>,
+}
+
+impl<const N: usize> MultiWordl<N> {
+    /// Plays `count` boards that each start out as `board`.
+    pub fn new(board: Wordl<N>, count: usize) -> Self {
+        MultiWordl {
+            boards: vec![board; count],
+        }
+    }
+
+    /// Plays the given boards, which may have different word lists.
+    pub fn from_boards(boards: Vec<Wordl<N>>) -> Self {
+        MultiWordl { boards }
+    }
+
+    pub fn boards(&self) -> &[Wordl<N>] {
+        &self.boards
+    }
+
+    /// Records `word` as a guess on every board, `feedbacks[i]` being the
+    /// feedback board `i` gave. Nothing is recorded if any feedback is for a
+    /// different word.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless there is exactly one feedback per board.
+    pub fn guess(&mut self, word: &str, feedbacks: &[Word<N>]) -> Result<(), WordlError> {
+        assert_eq!(
+            feedbacks.len(),
+            self.boards.len(),
+            "expected feedback for each of the {} boards",
+            self.boards.len()
+        );
+        let word = word.trim().to_lowercase();
+        if let Some(feedback) = feedbacks.iter().find(|f| f.letters() != word) {
+            return Err(WordlError::FeedbackMismatch {
+                guess: word,
+                feedback: feedback.letters(),
+            });
+        }
+        for (board, feedback) in self.boards.iter_mut().zip(feedbacks) {
+            board.guess(feedback.clone());
+        }
+        Ok(())
+    }
+
+    /// Returns true once every board is solved.
+    pub fn is_solved(&self) -> bool {
+        self.boards.iter().all(Wordl::is_solved)
+    }
+
+    /// Returns up to `upto` words, from any board's allowed words, ranked by
+    /// the expected information of their feedback summed over the unsolved
+    /// boards.
+    pub fn suggest(&self, upto: usize) -> Vec<String> {
+        let unsolved: Vec<&Wordl<N>> = self.boards.iter().filter(|b| !b.is_solved()).collect();
+        let probes: BTreeSet<&String> = unsolved.iter().flat_map(|b| b.probes()).collect();
+        let scored = score_all(probes, |probe| -> f64 {
+            unsolved
+                .iter()
+                .map(|b| {
+                    let buckets = b.remaining_buckets(probe);
+                    Wordl::<N>::bucket_entropy(b.answers.len(), buckets.values()).sum::<f64>()
+                })
+                .sum()
+        });
+        take_highest(scored, upto)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MultiWordl, Wordl, WordlError, DEFAULT_DICTIONARY};
+
+    #[test]
+    fn suggest_serves_every_board() {
+        let board = |answers: [&str; 4]| -> Wordl {
+            Wordl::from_lists(
+                answers.map(String::from),
+                DEFAULT_DICTIONARY.lines().map(String::from),
+            )
+        };
+        let mut m = MultiWordl::from_boards(vec![
+            board(["cants", "pants", "rants", "wants"]),
+            board(["bowel", "dowel", "towel", "vowel"]),
+        ]);
+        // "caper" and "bated" each tell one board's words apart, "bract"
+        // halves both
+        assert_eq!(m.boards()[0].suggest_any_by_entropy(1), vec!["caper"]);
+        assert_eq!(m.boards()[1].suggest_any_by_entropy(1), vec!["bated"]);
+        assert_eq!(m.suggest(1), vec!["bract"]);
+
+        // feedback for another word is refused and nothing is recorded
+        let mismatched = m.guess(
+            "bract",
+            &[
+                Wordl::evaluate("pants", "bract"),
+                Wordl::evaluate("dowel", "caper"),
+            ],
+        );
+        assert!(matches!(
+            mismatched,
+            Err(WordlError::FeedbackMismatch { guess, feedback })
+                if guess == "bract" && feedback == "caper"
+        ));
+        assert_eq!(m.boards()[0].remaining_count(), 4);
+
+        m.guess(
+            "bract",
+            &[
+                Wordl::evaluate("pants", "bract"),
+                Wordl::evaluate("dowel", "bract"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(m.boards()[0].candidates(), vec!["pants", "wants"]);
+        assert_eq!(m.boards()[1].candidates(), vec!["dowel", "vowel"]);
+        assert!(!m.is_solved());
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{Filter, Wordl};
+
+impl<const N: usize> Wordl<N> {
+    /// Counts the strings of `N` letters that fit every guess and required
+    /// letter so far, whether or not they are words, e.g. to see how much a
+    /// list that's missing the answer is leaving out. Letters come from the
+    /// alphabet, see `set_alphabet`, or a to z by default. The count
+    /// saturates at `u64::MAX`.
+    pub fn count_patterns(&self) -> u64 {
+        let filter = self.filter(&self.guesses);
+        let options = self.pattern_options(&filter);
+        // the letters whose count is constrained, and the count past which
+        // more of them makes no difference
+        let tracked: Vec<(char, usize, Option<usize>)> = filter
+            .min_counts
+            .keys()
+            .chain(filter.max_counts.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|c| {
+                let min = filter.min_counts.get(c).copied().unwrap_or(0);
+                (*c, min, filter.max_counts.get(c).copied())
+            })
+            .collect();
+        let cap = |idx: usize| {
+            let (_, min, max) = tracked[idx];
+            max.map_or(min, |max| max + 1).max(min)
+        };
+
+        // the number of prefixes for each count of the tracked letters
+        let mut counts: BTreeMap<Vec<usize>, u64> = BTreeMap::new();
+        counts.insert(vec![0; tracked.len()], 1);
+        for (pos, letters) in options.iter().enumerate() {
+            let left = N - pos - 1;
+            let mut next: BTreeMap<Vec<usize>, u64> = BTreeMap::new();
+            for (state, n) in &counts {
+                for c in letters {
+                    let mut state = state.clone();
+                    if let Some(idx) = tracked.iter().position(|(t, _, _)| t == c) {
+                        state[idx] = (state[idx] + 1).min(cap(idx));
+                        if tracked[idx].2.is_some_and(|max| state[idx] > max) {
+                            continue;
+                        }
+                    }
+                    if Self::still_needed(&tracked, &state) > left {
+                        continue;
+                    }
+                    let entry = next.entry(state).or_insert(0);
+                    *entry = entry.saturating_add(*n);
+                }
+            }
+            counts = next;
+        }
+        counts
+            .into_iter()
+            .filter(|(state, _)| Self::still_needed(&tracked, state) == 0)
+            .fold(0, |total, (_, n)| total.saturating_add(n))
+    }
+
+    /// Up to `limit` of the strings `count_patterns` counts, in alphabetical
+    /// order.
+    pub fn patterns(&self, limit: usize) -> Vec<String> {
+        let filter = self.filter(&self.guesses);
+        let options = self.pattern_options(&filter);
+        let mut result = Vec::new();
+        let mut prefix = String::new();
+        Self::extend_patterns(&filter, &options, &mut prefix, limit, &mut result);
+        result
+    }
+
+    // the letters each position may hold on their own, before counts
+    fn pattern_options(&self, filter: &Filter<N>) -> Vec<Vec<char>> {
+        let alphabet: BTreeSet<char> = match &self.alphabet {
+            Some(alphabet) => alphabet.clone(),
+            None => ('a'..='z').collect(),
+        };
+        (0..N)
+            .map(|idx| match filter.hits[idx] {
+                Some(c) => vec![c],
+                None => alphabet
+                    .iter()
+                    .filter(|c| {
+                        !filter.excludes.contains(c) && !filter.excludes_at[idx].contains(c)
+                    })
+                    .copied()
+                    .collect(),
+            })
+            .collect()
+    }
+
+    // how many more letters the minimum counts call for, given `state` from
+    // `count_patterns`
+    fn still_needed(tracked: &[(char, usize, Option<usize>)], state: &[usize]) -> usize {
+        tracked
+            .iter()
+            .zip(state)
+            .map(|((_, min, _), n)| min.saturating_sub(*n))
+            .sum()
+    }
+
+    // completes `prefix` depth first, giving up on prefixes that already have
+    // too many of a letter or too few places left for the required ones
+    fn extend_patterns(
+        filter: &Filter<N>,
+        options: &[Vec<char>],
+        prefix: &mut String,
+        limit: usize,
+        result: &mut Vec<String>,
+    ) {
+        if result.len() >= limit {
+            return;
+        }
+        let pos = prefix.chars().count();
+        if pos == N {
+            if filter.is_valid(prefix) {
+                result.push(prefix.clone());
+            }
+            return;
+        }
+        for c in &options[pos] {
+            prefix.push(*c);
+            let count = |l: &char| prefix.chars().filter(|p| p == l).count();
+            let too_many = filter.max_counts.iter().any(|(l, max)| count(l) > *max);
+            let needed: usize = filter
+                .min_counts
+                .iter()
+                .map(|(l, min)| min.saturating_sub(count(l)))
+                .sum();
+            if !too_many && needed < N - pos {
+                Self::extend_patterns(filter, options, prefix, limit, result);
+            }
+            prefix.pop();
+            if result.len() >= limit {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Wordl;
+
+    #[test]
+    fn patterns_fill_the_open_positions() {
+        let mut w = Wordl::new();
+        w.insert("slump".to_string());
+        // "slum" is placed and the last letter is any but s, c, r, a, n or e
+        w.guess(Wordl::evaluate("slump", "slums"));
+        w.guess(Wordl::evaluate("slump", "crane"));
+        assert_eq!(w.count_patterns(), 20);
+        let patterns = w.patterns(usize::MAX);
+        assert_eq!(patterns.len(), 20);
+        assert_eq!(patterns[..3], ["slumb", "slumd", "slumf"]);
+        assert!(patterns.contains(&"slump".to_string()));
+        assert!(!patterns.contains(&"slums".to_string()));
+    }
+
+    #[test]
+    fn patterns_respect_counts_and_the_alphabet() {
+        let mut w = Wordl::new();
+        w.set_alphabet("abcde".chars());
+        // "ab" is placed, the yellow b can't be fourth and the gray one
+        // caps b at two and rules out the fifth place, so the word starts
+        // "abb". c is out, so the last two letters are any of a, d and e:
+        // 3 * 3 patterns
+        let feedback = Wordl::evaluate("abbde", "abcbb");
+        w.guess(feedback.clone());
+        let patterns = w.patterns(100);
+        assert!(patterns
+            .iter()
+            .all(|p| Wordl::evaluate(p, "abcbb") == feedback));
+        assert_eq!(patterns.len() as u64, w.count_patterns());
+        assert_eq!(patterns.len(), 9);
+        assert!(patterns
+            .iter()
+            .all(|p| p.starts_with("abb") && !p[3..].contains(['b', 'c'])));
+        assert_eq!(w.patterns(3), vec!["abbaa", "abbad", "abbae"]);
+    }
+
+    #[test]
+    fn count_patterns_matches_enumeration() {
+        let mut w = Wordl::with_default_dictionary();
+        w.guess(Wordl::evaluate("slump", "crane"));
+        w.guess(Wordl::evaluate("slump", "moist"));
+        w.guess(Wordl::evaluate("slump", "lumps"));
+        let count = w.count_patterns();
+        assert!(count > 0);
+        assert_eq!(w.patterns(usize::MAX).len() as u64, count);
+        assert_eq!(Wordl::<5>::new().count_patterns(), 26u64.pow(5));
+    }
+}
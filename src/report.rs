@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+use crate::Wordl;
+
+/// How many guesses `auto_solve` needs for each word of the dictionary, see
+/// `Wordl::difficulty_report`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DifficultyReport {
+    /// The number of guesses each answer took, including the final one.
+    /// Answers `auto_solve` gave up on are left out and listed in `unsolved`.
+    pub guesses: BTreeMap<String, usize>,
+    pub unsolved: Vec<String>,
+}
+
+impl DifficultyReport {
+    /// The mean number of guesses over the solved answers, 0 when none were.
+    pub fn mean(&self) -> f64 {
+        let total: usize = self.guesses.values().sum();
+        total as f64 / self.guesses.len().max(1) as f64
+    }
+
+    /// The most guesses any answer took.
+    pub fn max(&self) -> usize {
+        self.guesses.values().copied().max().unwrap_or(0)
+    }
+
+    /// The share of answers, between 0 and 1, solved in `limit` guesses or
+    /// fewer.
+    pub fn solved_within(&self, limit: usize) -> f64 {
+        let within = self.guesses.values().filter(|n| **n <= limit).count();
+        within as f64 / (self.guesses.len() + self.unsolved.len()).max(1) as f64
+    }
+
+    /// The `upto` answers that took the most guesses, hardest first.
+    pub fn hardest(&self, upto: usize) -> Vec<(&str, usize)> {
+        let mut result: Vec<(&str, usize)> =
+            self.guesses.iter().map(|(w, n)| (w.as_str(), *n)).collect();
+        // stable, so equally hard answers stay alphabetical
+        result.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+        result.truncate(upto);
+        result
+    }
+}
+
+impl<const N: usize> Wordl<N> {
+    /// Plays every word of the dictionary as the answer with `auto_solve`,
+    /// using this solver's settings but none of its guesses. Answers are
+    /// played in parallel with the `rayon` feature; without it this takes a
+    /// while for a full dictionary.
+    pub fn difficulty_report(&self) -> DifficultyReport {
+        let mut fresh = self.clone();
+        fresh.reset();
+        let play = |answer: &String| {
+            let mut game = fresh.clone();
+            let guesses = game.auto_solve(answer);
+            let solved = guesses.last() == Some(answer);
+            (answer.clone(), solved.then_some(guesses.len()))
+        };
+        #[cfg(feature = "rayon")]
+        let results: Vec<(String, Option<usize>)> = {
+            use rayon::prelude::*;
+            let answers: Vec<&String> = self.dictionary.iter().collect();
+            answers.into_par_iter().map(play).collect()
+        };
+        #[cfg(not(feature = "rayon"))]
+        let results: Vec<(String, Option<usize>)> = self.dictionary.iter().map(play).collect();
+
+        let mut report = DifficultyReport {
+            guesses: BTreeMap::new(),
+            unsolved: vec![],
+        };
+        for (answer, guesses) in results {
+            match guesses {
+                Some(n) => {
+                    report.guesses.insert(answer, n);
+                }
+                None => report.unsolved.push(answer),
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Word, Wordl};
+
+    #[test]
+    fn report_covers_every_word_once() {
+        let mut w = Wordl::new();
+        for word in ["cants", "pants", "rants", "wants", "slump", "solum"] {
+            w.insert(word.to_string());
+        }
+        // earlier guesses don't carry over into the report
+        w.guess(Word::parse("slump", "HHHHH").unwrap());
+        let report = w.difficulty_report();
+        assert!(report.unsolved.is_empty());
+        assert_eq!(
+            report.guesses.keys().collect::<Vec<_>>(),
+            w.dictionary().collect::<Vec<_>>()
+        );
+        for (answer, n) in &report.guesses {
+            let mut game = w.clone();
+            game.reset();
+            assert_eq!(game.auto_solve(answer).len(), *n, "{}", answer);
+        }
+        assert_eq!(report.max(), report.hardest(1)[0].1);
+        assert_eq!(report.solved_within(report.max()), 1.0);
+        assert!(report.mean() >= 1.0 && report.mean() <= report.max() as f64);
+    }
+}
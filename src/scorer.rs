@@ -0,0 +1,98 @@
+use crate::{Strategy, Wordl};
+
+/// Scores a candidate word against the state of a game, for
+/// `Wordl::set_scorer`. Scoring may run on several threads with the `rayon`
+/// feature, hence `Send + Sync`.
+///
+/// ```
+/// use wordl::{Scorer, Wordl};
+///
+/// // prefers words with many vowels
+/// struct Vowels;
+///
+/// impl Scorer for Vowels {
+///     fn score(&self, candidate: &str, _state: &Wordl) -> f64 {
+///         candidate.chars().filter(|c| "aeiou".contains(*c)).count() as f64
+///     }
+/// }
+///
+/// let mut w = Wordl::new();
+/// for word in ["crwth", "adieu", "slate"] {
+///     w.insert(word.to_string());
+/// }
+/// w.set_scorer(Vowels);
+/// assert_eq!(w.suggest(3), vec!["adieu", "slate", "crwth"]);
+/// ```
+pub trait Scorer<const N: usize = 5>: Send + Sync {
+    fn score(&self, candidate: &str, state: &Wordl<N>) -> f64;
+
+    /// Whether higher scores rank first, the default, or last.
+    fn higher_is_better(&self) -> bool {
+        true
+    }
+}
+
+// the built-in scorers, which the `suggest_by_*` methods and `explain` all
+// rank with
+impl<const N: usize> Scorer<N> for Strategy {
+    fn score(&self, candidate: &str, state: &Wordl<N>) -> f64 {
+        match self {
+            Strategy::Frequency => state.positional_score(candidate, &state.solved_positions()),
+            Strategy::Entropy => state.entropy(candidate),
+            Strategy::Minimax => state.worst_bucket(candidate) as f64,
+            Strategy::LetterCoverage => state
+                .coverage_parts(candidate)
+                .into_iter()
+                .map(|(_, part)| part)
+                .sum(),
+            Strategy::Commonness => state.commonness_of(candidate),
+        }
+    }
+
+    fn higher_is_better(&self) -> bool {
+        *self != Strategy::Minimax
+    }
+}
+
+// positional frequency for ranking probes. Unlike the candidates, probes
+// don't all agree at the solved positions, so every position counts.
+pub(crate) struct ProbeFrequency;
+
+impl<const N: usize> Scorer<N> for ProbeFrequency {
+    fn score(&self, candidate: &str, state: &Wordl<N>) -> f64 {
+        state.positional_score(candidate, &[false; N])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Scorer, Strategy, Wordl};
+
+    // ranks words by how late in the alphabet their last letter is
+    struct LastLetter;
+
+    impl Scorer for LastLetter {
+        fn score(&self, candidate: &str, _state: &Wordl) -> f64 {
+            candidate.chars().last().map_or(0.0, |c| c as u32 as f64)
+        }
+    }
+
+    #[test]
+    fn suggest_ranks_with_a_custom_scorer() {
+        let mut w = Wordl::with_default_dictionary();
+        w.guess(Wordl::evaluate("slump", "crane"));
+        let frequency = w.suggest(5);
+
+        w.set_scorer(LastLetter);
+        let scores: Vec<f64> = w.suggest(5).iter().map(|s| w.explain(s).score).collect();
+        let best = w
+            .remaining()
+            .map(|s| LastLetter.score(s, &w))
+            .fold(0.0, f64::max);
+        assert_eq!(scores[0], best);
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        w.set_strategy(Strategy::Frequency);
+        assert_eq!(w.suggest(5), frequency);
+    }
+}
@@ -0,0 +1,96 @@
+//! Colored terminal rendering, enabled with the `tui` feature.
+//!
+//! The grid is drawn with plain ANSI escape sequences, so the feature adds no
+//! dependencies. `cargo run --features tui -- --interactive` redraws it after
+//! every guess.
+
+use crate::{Letter, Word, Wordl};
+
+// black text on a green, yellow or gray background, then back to normal
+const HIT: &str = "\x1b[30;42m";
+const CONTAINS: &str = "\x1b[30;43m";
+const MISS: &str = "\x1b[30;47m";
+const RESET: &str = "\x1b[0m";
+
+/// Renders a guess as one colored cell per letter: green for a hit, yellow
+/// for contains and gray for a miss. Unknown letters are left uncolored.
+///
+/// ```
+/// use wordl::{tui, Word};
+///
+/// let word: Word = Word::parse("souls", "HMMMM").unwrap();
+/// assert!(tui::render_row(&word).starts_with("\x1b[30;42m S \x1b[0m"));
+/// ```
+pub fn render_row<const N: usize>(word: &Word<N>) -> String {
+    word.0
+        .iter()
+        .map(|l| {
+            let (color, c) = match l {
+                Letter::Hit(c) => (HIT, c),
+                Letter::Contains(c) => (CONTAINS, c),
+                Letter::Miss(c) => (MISS, c),
+                Letter::Unknown(c) => return format!(" {} ", c.to_uppercase()),
+            };
+            format!("{} {} {}", color, c.to_uppercase(), RESET)
+        })
+        .collect()
+}
+
+/// Renders the guesses so far, one row each, followed by how many
+/// candidates are left and up to `upto` suggestions.
+pub fn render<const N: usize>(w: &Wordl<N>, upto: usize) -> String {
+    let mut result = String::new();
+    for word in &w.guesses {
+        result.push_str(&render_row(word));
+        result.push('\n');
+    }
+    if !w.guesses.is_empty() {
+        result.push('\n');
+    }
+    result.push_str(&format!("{} possibilities left\n", w.remaining_count()));
+    for s in w.suggest(upto) {
+        result.push_str(&format!("suggestion: {}\n", s));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tui::{render, render_row};
+    use crate::{Word, Wordl};
+
+    #[test]
+    fn render_row_colors_each_letter() {
+        let word: Word = Word::parse("souls", "HMC?C").unwrap();
+        assert_eq!(
+            render_row(&word),
+            "\x1b[30;42m S \x1b[0m\
+             \x1b[30;47m O \x1b[0m\
+             \x1b[30;43m U \x1b[0m \
+             L \
+             \x1b[30;43m S \x1b[0m"
+        );
+    }
+
+    #[test]
+    fn render_lists_guesses_and_suggestions() {
+        let mut w = Wordl::new();
+        for word in ["crane", "slump", "stump"] {
+            w.insert(word.to_string());
+        }
+        let word = Wordl::<5>::evaluate("slump", "crane");
+        w.guess(word.clone());
+        let screen = render(&w, 3);
+        let lines: Vec<&str> = screen.lines().collect();
+        assert_eq!(lines[0], render_row(&word));
+        assert_eq!(
+            lines[1..],
+            [
+                "",
+                "2 possibilities left",
+                "suggestion: slump",
+                "suggestion: stump"
+            ]
+        );
+    }
+}
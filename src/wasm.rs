@@ -0,0 +1,71 @@
+//! WebAssembly bindings, enabled with the `wasm` feature.
+//!
+//! Build with `wasm-pack build --target web -- --features wasm` and load the
+//! solver from a newline separated word list:
+//!
+//! ```js
+//! import init, { WasmWordl } from "./pkg/wordl.js";
+//!
+//! await init();
+//! const words = await (await fetch("words.txt")).text();
+//! const solver = new WasmWordl(words);
+//! solver.guess("souls", "HMCCC");
+//! console.log(solver.suggest(3));
+//! ```
+//!
+//! `examples/wasm/index.html` is a page doing the same.
+
+use wasm_bindgen::prelude::*;
+
+use crate::Wordl;
+
+/// A five-letter `Wordl` exposed to JavaScript.
+#[wasm_bindgen]
+pub struct WasmWordl {
+    inner: Wordl,
+}
+
+#[wasm_bindgen]
+impl WasmWordl {
+    /// Creates a solver from a newline separated dictionary.
+    #[wasm_bindgen(constructor)]
+    pub fn new(dictionary: &str) -> WasmWordl {
+        let mut inner = Wordl::new();
+        for word in dictionary.lines() {
+            inner.insert(word.to_string());
+        }
+        WasmWordl { inner }
+    }
+
+    /// Applies a guess with `M`/`C`/`H` or emoji feedback, see `Word::parse`.
+    pub fn guess(&mut self, word: &str, feedback: &str) -> Result<(), String> {
+        self.inner
+            .guess_str(word, feedback)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Returns up to `upto` suggestions as an array of strings.
+    pub fn suggest(&self, upto: usize) -> Vec<String> {
+        self.inner.suggest(upto)
+    }
+
+    /// The number of words still consistent with the guesses.
+    #[wasm_bindgen(js_name = remainingCount)]
+    pub fn remaining_count(&self) -> usize {
+        self.inner.remaining_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::wasm::WasmWordl;
+
+    #[test]
+    fn guess_narrows_suggestions() {
+        let mut w = WasmWordl::new("souls\nsolum\nslump\nmould\nsumps\n");
+        assert_eq!(w.remaining_count(), 5);
+        w.guess("souls", "HMHCM").unwrap();
+        assert_eq!(w.suggest(3), vec!["slump"]);
+        assert!(w.guess("souls", "HMXCM").is_err());
+    }
+}